@@ -0,0 +1,33 @@
+//! Compare `Universe::clear` with clearing cells one by one.
+//! Run with `cargo bench --bench clear`.
+
+use std::{hint::black_box, time::Instant};
+
+use wasm_game_of_life::{Cell, Universe};
+
+const SIZE: u32 = 1024;
+const ROUNDS: u32 = 200;
+
+fn main() {
+    let mut universe = Universe::new();
+    universe.set_width(SIZE);
+    universe.set_height(SIZE);
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        universe.clear();
+        black_box(&universe);
+    }
+    let fill = start.elapsed() / ROUNDS;
+
+    let mut cells = vec![Cell::Alive; (SIZE * SIZE) as usize];
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        for cell in black_box(&mut cells).iter_mut() {
+            *cell = black_box(Cell::Dead);
+        }
+    }
+    let per_cell = start.elapsed() / ROUNDS;
+
+    println!("clear (fill):     {:?} per call", fill);
+    println!("per-cell loop:    {:?} per call", per_cell);
+}
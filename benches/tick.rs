@@ -0,0 +1,33 @@
+//! Compare tick strategies on a random board.
+//! Run with `cargo bench --bench tick`.
+
+use std::time::Instant;
+
+use wasm_game_of_life::{Boundary, TickStrategy, Universe};
+
+const SIZE: u32 = 512;
+const TICKS: u32 = 50;
+
+fn main() {
+    for boundary in [Boundary::Toroidal, Boundary::Dead].iter() {
+        for strategy in [TickStrategy::Scalar, TickStrategy::RowSums].iter() {
+            let mut universe = Universe::new();
+            universe.set_width(SIZE);
+            universe.set_height(SIZE);
+            universe.seed_stream(1);
+            universe.randomize();
+            universe.set_boundary(*boundary);
+            universe.set_tick_strategy(*strategy);
+            let start = Instant::now();
+            for _ in 0..TICKS {
+                universe.tick();
+            }
+            println!(
+                "{:?} / {:?}: {:?} per tick",
+                boundary,
+                strategy,
+                start.elapsed() / TICKS
+            );
+        }
+    }
+}
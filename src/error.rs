@@ -0,0 +1,58 @@
+use std::{error::Error, fmt};
+
+use wasm_bindgen::JsValue;
+
+/// Reasons why an operation on a universe or pattern fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LifeError {
+    /// A pattern, or the part of a board it's taken from, has no live cells.
+    EmptyPattern,
+    /// Text input is malformed at the given line, counting from 1.
+    ParseError { line: usize, msg: String },
+    /// A board or pattern of size `found` doesn't agree with or fit into `expected`.
+    /// Both sizes are given as `(width, height)`.
+    DimensionMismatch {
+        expected: (u32, u32),
+        found: (u32, u32),
+    },
+    /// A cell lies outside the board.
+    OutOfBounds { row: u32, column: u32 },
+    /// The universe is frozen and can't be edited.
+    Frozen,
+    /// An argument is outside the range the operation supports.
+    InvalidArgument(String),
+    /// Binary input, a serialized board or an image, can't be decoded.
+    InvalidData(String),
+    /// Internal state is inconsistent, see `Universe::debug_invariants`.
+    BrokenInvariant(String),
+}
+
+impl fmt::Display for LifeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LifeError::EmptyPattern => write!(f, "No alive cells in the pattern"),
+            LifeError::ParseError { line, msg } => write!(f, "Line {}: {}", line, msg),
+            LifeError::DimensionMismatch { expected, found } => write!(
+                f,
+                "Size {}x{} is incompatible with {}x{}",
+                found.0, found.1, expected.0, expected.1
+            ),
+            LifeError::OutOfBounds { row, column } => {
+                write!(f, "Cell ({}, {}) is out of the grid", row, column)
+            }
+            LifeError::Frozen => write!(f, "The universe is frozen"),
+            LifeError::InvalidArgument(msg)
+            | LifeError::InvalidData(msg)
+            | LifeError::BrokenInvariant(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl Error for LifeError {}
+
+/// Errors cross the WASM boundary as their messages.
+impl From<LifeError> for JsValue {
+    fn from(error: LifeError) -> JsValue {
+        JsValue::from_str(&error.to_string())
+    }
+}
@@ -1,10 +1,29 @@
+mod error;
+mod patterns;
+#[cfg(any(feature = "image-import", feature = "image-export"))]
+mod png;
+mod rng;
+mod rule;
+mod sparse;
 mod utils;
 
-use std::{mem::swap, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    convert::TryInto,
+    fmt,
+    mem::{size_of, swap},
+    ops::Range,
+    str::FromStr,
+};
 
-use rand::random;
+use rng::Rng;
+use rule::Rule;
 use wasm_bindgen::prelude::*;
 
+pub use error::LifeError;
+pub use sparse::SparseUniverse;
+
+#[cfg(feature = "wee-alloc")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
@@ -25,18 +44,198 @@ impl Cell {
     }
 }
 
-#[derive(Debug)]
-struct Pattern {
+/// Largest bounding box, in cells, of a parsed RLE pattern,
+/// so a short string can't claim an enormous one.
+const RLE_CELL_LIMIT: u64 = 1 << 24;
+
+/// A group of cells that can be inserted into a universe.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
     alive_cells: Vec<(u32, u32)>,
     width: u32,
     height: u32,
 }
 
+impl Pattern {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Coordinates of alive cells as `(row, column)` relative to the top left corner.
+    pub fn alive_cells(&self) -> &[(u32, u32)] {
+        &self.alive_cells
+    }
+
+    /// Number of cells the pattern brings to life when inserted.
+    pub fn population(&self) -> usize {
+        self.alive_cells.len()
+    }
+
+    /// Pattern turned clockwise by a quarter turn `quarter_turns` times.
+    #[cfg(feature = "json-scene")]
+    fn rotated(&self, quarter_turns: u32) -> Pattern {
+        let mut pattern = self.clone();
+        for _ in 0..quarter_turns % 4 {
+            pattern = Pattern {
+                alive_cells: pattern
+                    .alive_cells
+                    .iter()
+                    .map(|(x, y)| (*y, pattern.height - 1 - x))
+                    .collect(),
+                width: pattern.height,
+                height: pattern.width,
+            };
+        }
+        pattern
+    }
+
+    /// Pattern flipped left to right and/or top to bottom within its bounding box.
+    fn mirrored(&self, left_right: bool, top_bottom: bool) -> Pattern {
+        let alive_cells = self
+            .alive_cells
+            .iter()
+            .map(|(x, y)| {
+                (
+                    if top_bottom { self.height - 1 - x } else { *x },
+                    if left_right { self.width - 1 - y } else { *y },
+                )
+            })
+            .collect();
+        Pattern {
+            alive_cells,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Parse a pattern in run length encoded format: `b` is a dead cell, `o` an alive one,
+    /// `$` ends a row and `!` ends the pattern. Lines starting with `#` are comments.
+    /// Size given in the `x = .., y = ..` header is kept if it exceeds the alive cells.
+    /// Patterns spanning more than 2^24 cells are rejected.
+    pub fn from_rle(rle: &str) -> Result<Pattern, LifeError> {
+        let mut lines = rle
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .filter(|(_, l)| !l.is_empty() && !l.starts_with('#'))
+            .peekable();
+        let (mut width, mut height) = (1, 1);
+        if let Some((line, header)) = lines.next_if(|(_, l)| l.starts_with('x')) {
+            for field in header.split(',') {
+                let mut parts = field.splitn(2, '=').map(str::trim);
+                let (key, value) = (parts.next(), parts.next());
+                let size = || {
+                    value
+                        .and_then(|v| v.parse::<u32>().ok())
+                        .ok_or_else(|| LifeError::ParseError {
+                            line,
+                            msg: format!("Invalid RLE header: [{}]", header),
+                        })
+                };
+                match key {
+                    Some("x") => width = size()?,
+                    Some("y") => height = size()?,
+                    _ => {}
+                }
+            }
+            if width as u64 * height as u64 > RLE_CELL_LIMIT {
+                return Err(LifeError::ParseError {
+                    line,
+                    msg: format!("RLE pattern is too large: {}x{}", width, height),
+                });
+            }
+        }
+        let mut alive_cells = vec![];
+        let (mut row, mut column) = (0u32, 0u32);
+        let mut count = String::new();
+        'body: for (line, text) in lines {
+            for c in text.chars() {
+                if c.is_ascii_digit() {
+                    count.push(c);
+                    continue;
+                }
+                let run: u32 = if count.is_empty() {
+                    1
+                } else {
+                    count.parse().map_err(|_| LifeError::ParseError {
+                        line,
+                        msg: format!("Invalid RLE run: {}", count),
+                    })?
+                };
+                count.clear();
+                let too_large = || LifeError::ParseError {
+                    line,
+                    msg: "RLE pattern is too large".to_string(),
+                };
+                match c {
+                    'b' => column = column.checked_add(run).ok_or_else(too_large)?,
+                    'o' => {
+                        let end = column
+                            .checked_add(run)
+                            .filter(|end| (row as u64 + 1) * *end as u64 <= RLE_CELL_LIMIT)
+                            .ok_or_else(too_large)?;
+                        alive_cells.extend((column..end).map(|j| (row, j)));
+                        column = end;
+                    }
+                    '$' => {
+                        row = row.checked_add(run).ok_or_else(too_large)?;
+                        column = 0;
+                    }
+                    '!' => break 'body,
+                    c if c.is_whitespace() => {}
+                    c => {
+                        return Err(LifeError::ParseError {
+                            line,
+                            msg: format!("Unexpected character in RLE pattern: {}", c),
+                        })
+                    }
+                }
+            }
+        }
+        if alive_cells.is_empty() {
+            return Err(LifeError::EmptyPattern);
+        }
+        for (x, y) in &alive_cells {
+            height = height.max(x + 1);
+            width = width.max(y + 1);
+        }
+        Ok(Pattern {
+            alive_cells,
+            width,
+            height,
+        })
+    }
+}
+
+/// Render the pattern in plaintext format.
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rows = vec![vec!['.'; self.width as usize]; self.height as usize];
+        for (row, col) in &self.alive_cells {
+            rows[*row as usize][*col as usize] = 'O';
+        }
+        for row in rows {
+            writeln!(f, "{}", row.into_iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a pattern in plaintext format: `O` is an alive cell,
+/// lines starting with `!` are comments.
+/// Blank lines inside the pattern are kept as empty rows,
+/// while leading and trailing ones are ignored.
 impl FromStr for Pattern {
     fn from_str(schema: &str) -> Result<Self, Self::Err> {
         let alive_cells: Vec<(u32, u32)> = schema
             .lines()
-            .filter(|l| !l.starts_with("!"))
+            .filter(|l| !l.starts_with('!'))
+            .skip_while(|l| l.trim().is_empty())
             .enumerate()
             .flat_map(|(i, l)| {
                 l.char_indices()
@@ -45,9 +244,9 @@ impl FromStr for Pattern {
             })
             .collect();
         if alive_cells.is_empty() {
-            return Err(format!("No alive cells in given pattern: [{}]", schema));
+            return Err(LifeError::EmptyPattern);
         }
-        let (width, height) = alive_cells.iter().fold((1, 1), |(xmax, ymax), (x, y)| {
+        let (height, width) = alive_cells.iter().fold((1, 1), |(xmax, ymax), (x, y)| {
             (xmax.max(*x + 1), ymax.max(*y + 1))
         });
         Ok(Self {
@@ -57,35 +256,383 @@ impl FromStr for Pattern {
         })
     }
 
-    type Err = String;
+    type Err = LifeError;
+}
+
+/// One pattern of a scene loaded by `Universe::load_scene_json`.
+#[cfg(feature = "json-scene")]
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScenePlacement {
+    /// Name of a known pattern, or a pattern in plaintext format.
+    pattern: String,
+    row: u32,
+    col: u32,
+    /// Clockwise rotation in degrees, a multiple of 90.
+    #[serde(default)]
+    rotation: u32,
+}
+
+/// Changes of the last tick for the renderer, see `Universe::diff_or_full`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiffResult {
+    /// Flat indices of the changed cells, relative to the origin.
+    Changes(Vec<u32>),
+    /// So many cells changed that redrawing the whole board is cheaper.
+    Full,
+}
+
+/// Which color a newborn takes when several colors are equally common among its parents.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorTiebreak {
+    /// The lowest tied color index wins.
+    Lowest,
+    /// The highest tied color index wins.
+    Highest,
+}
+
+/// How `tick` counts live neighbors. Both strategies give identical results.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TickStrategy {
+    /// Count the eight neighbors of each cell one by one.
+    Scalar,
+    /// Count neighbors of whole rows at once by summing shifted copies of them.
+    /// Falls back to `Scalar` on the Möbius boundary.
+    RowSums,
+}
+
+/// Order in which `tick_async` visits cells. Synchronous ticks don't depend on it.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScanOrder {
+    /// A random order drawn from the seed passed to `tick_async`.
+    Shuffled,
+    /// Row by row, each from left to right.
+    RowMajor,
+    /// Column by column, each from top to bottom.
+    ColumnMajor,
+    /// Row by row, alternating left to right and right to left.
+    BoustrophedonRow,
+}
+
+/// Axes across the board center along which `insert_pattern_mirrored` reflects a pattern.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Symmetry {
+    /// Mirror left to right, across the vertical center line.
+    Horizontal,
+    /// Mirror top to bottom, across the horizontal center line.
+    Vertical,
+    /// Mirror across both center lines, giving four copies.
+    Both,
+}
+
+/// How a pattern is combined with the cells it's inserted over.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Blend {
+    /// Clear the pattern's bounding box before setting its live cells.
+    Overwrite,
+    /// Only set the pattern's live cells, keeping the background.
+    Or,
+}
+
+/// How neighbors are looked up for cells on the edge of the grid.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    /// The grid wraps around: the last column neighbors the first one.
+    Toroidal,
+    /// Everything beyond the edge is permanently dead.
+    Dead,
+    /// Möbius strip: the left and right edges are joined with a flip,
+    /// so leaving through row `r` re-enters at row `height - 1 - r`.
+    /// Beyond the top and bottom edges cells are dead.
+    Mobius,
+}
+
+impl Boundary {
+    /// Cell reached at a possibly off-grid position, or `None` if it's beyond a dead edge.
+    pub(crate) fn resolve(
+        self,
+        row: i64,
+        column: i64,
+        height: u32,
+        width: u32,
+    ) -> Option<(u32, u32)> {
+        let (h, w) = (height as i64, width as i64);
+        match self {
+            Boundary::Toroidal => Some((row.rem_euclid(h) as u32, column.rem_euclid(w) as u32)),
+            _ if !(0..h).contains(&row) => None,
+            _ if (0..w).contains(&column) => Some((row as u32, column as u32)),
+            Boundary::Dead => None,
+            Boundary::Mobius => Some(((h - 1 - row) as u32, column.rem_euclid(w) as u32)),
+        }
+    }
+}
+
+/// Variant of the game: classic Life or one of its colored versions,
+/// where live cells carry a color and newborns inherit the majority color of their parents.
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Classic,
+    /// Two colors of live cells: 0 and 1.
+    Immigration,
+    /// Four colors of live cells, 0 through 3. When all three parents differ,
+    /// the newborn takes the remaining fourth color.
+    QuadLife,
+}
+
+impl Mode {
+    fn colors(self) -> u8 {
+        match self {
+            Mode::Classic => 1,
+            Mode::Immigration => 2,
+            Mode::QuadLife => 4,
+        }
+    }
+}
+
+/// Smallest range `(start, length)` covering all occupied positions on an axis,
+/// allowed to continue past the end back to the start when wrapping.
+fn occupied_span(occupied: &[bool], wrap: bool) -> Option<(u32, u32)> {
+    let first = occupied.iter().position(|o| *o)?;
+    let last = occupied.iter().rposition(|o| *o)?;
+    let (mut start, mut length) = (first, last - first + 1);
+    if wrap {
+        // Start right after the longest circular run of empty positions.
+        let n = occupied.len();
+        let (mut gap, mut longest_gap) = (0, n - length);
+        for (i, o) in occupied.iter().enumerate().take(last + 1).skip(first) {
+            if *o {
+                if gap > longest_gap {
+                    longest_gap = gap;
+                    start = i;
+                    length = n - gap;
+                }
+                gap = 0;
+            } else {
+                gap += 1;
+            }
+        }
+    }
+    Some((start as u32, length as u32))
+}
+
+/// Number of cells of a `width`x`height` board, or `None` if it doesn't fit into `u32`.
+fn cell_count(width: u32, height: u32) -> Option<usize> {
+    width.checked_mul(height).map(|size| size as usize)
+}
+
+/// Run of `count` equal RLE tags, with the count omitted when it's one.
+fn run_token(count: usize, tag: char) -> String {
+    if count == 1 {
+        tag.to_string()
+    } else {
+        format!("{}{}", count, tag)
+    }
+}
+
+/// Cells on the line between two points, computed with Bresenham's algorithm.
+fn line_points(r0: u32, c0: u32, r1: u32, c1: u32) -> Vec<(u32, u32)> {
+    let (mut r, mut c) = (r0 as i64, c0 as i64);
+    let (r1, c1) = (r1 as i64, c1 as i64);
+    let dr = -(r1 - r).abs();
+    let dc = (c1 - c).abs();
+    let step_r = if r < r1 { 1 } else { -1 };
+    let step_c = if c < c1 { 1 } else { -1 };
+    let mut error = dc + dr;
+    let mut points = vec![];
+    loop {
+        points.push((r as u32, c as u32));
+        if r == r1 && c == c1 {
+            return points;
+        }
+        let e2 = 2 * error;
+        if e2 >= dr {
+            error += dr;
+            c += step_c;
+        }
+        if e2 <= dc {
+            error += dc;
+            r += step_r;
+        }
+    }
 }
 
-const WIDTH: u32 = 120;
-const HEIGHT: u32 = 120;
-const SIZE: usize = 120 * 120;
+const DEFAULT_WIDTH: u32 = 120;
+const DEFAULT_HEIGHT: u32 = 120;
+const DEFAULT_CELL_SIZE: u32 = 5;
+
+/// Bit set on `diff` entries of border cells when `set_wrap_diff` is on.
+const WRAP_DIFF_FLAG: i32 = 1 << 30;
 
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct Universe {
-    cells: [Cell; SIZE],
-    buffer: [Cell; SIZE],
-    diff: [i32; SIZE],
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+    buffer: Vec<Cell>,
+    diff: Vec<i32>,
+    diff_count: usize,
+    /// Whether each cell is already listed in `diff`.
+    in_diff: Vec<bool>,
+    diff_accumulate: bool,
+    /// Whether `diff` entries of border cells carry `WRAP_DIFF_FLAG`.
+    wrap_diff: bool,
+    wrapped_changes: Vec<u32>,
+    fade: Vec<u8>,
+    tags: Vec<u8>,
+    /// Generations each live cell has survived since it was born.
+    ages: Vec<u16>,
+    colors: Vec<u8>,
+    color_buffer: Vec<u8>,
+    mode: Mode,
+    color_tiebreak: ColorTiebreak,
+    rng: Rng,
+    /// Probabilities that a rule-dictated birth and death actually happen,
+    /// with the generator deciding it.
+    noise: Option<(f64, f64, Rng)>,
+    boundary: Boundary,
+    /// Cell shown at the top left corner by views of the board.
+    origin: (u32, u32),
+    /// Size of a cell in pixels, for renderers only.
+    cell_size: u32,
+    tick_strategy: TickStrategy,
+    scan_order: ScanOrder,
+    /// Whether ticks and edits of the cells are ignored.
+    frozen: bool,
+    rule: Rule,
+    /// Survival counts of the rule as set by `set_rule`, before `set_overpopulation` limits them.
+    rule_survival: [bool; 9],
+    /// Rules of regions set by `set_rule_region`.
+    region_rules: Vec<Rule>,
+    /// Per cell, 0 for the main rule or one plus the index into `region_rules`.
+    rule_index: Vec<u8>,
+    generation: u64,
+    /// Consecutive ticks that changed no cell.
+    unchanged_ticks: u64,
+    snapshot_every: u64,
+    snapshots: BTreeMap<u64, (Vec<Cell>, Vec<u8>)>,
+    max_population: u32,
+    min_population: u32,
+    /// Commands applied since `start_recording`, one per line.
+    recording: Option<Vec<String>>,
 }
 
 impl Universe {
+    fn empty(width: u32, height: u32) -> Universe {
+        let size = cell_count(width, height)
+            .unwrap_or_else(|| panic!("A {}x{} board has too many cells", width, height));
+        Universe {
+            width,
+            height,
+            cells: vec![Cell::Dead; size],
+            buffer: vec![Cell::Dead; size],
+            diff: vec![-1; size],
+            diff_count: 0,
+            in_diff: vec![false; size],
+            diff_accumulate: false,
+            wrap_diff: false,
+            wrapped_changes: vec![],
+            fade: vec![0; size],
+            tags: vec![0; size],
+            ages: vec![0; size],
+            colors: vec![0; size],
+            color_buffer: vec![0; size],
+            mode: Mode::Classic,
+            color_tiebreak: ColorTiebreak::Highest,
+            rng: Rng::from_time(),
+            noise: None,
+            boundary: Boundary::Toroidal,
+            origin: (0, 0),
+            cell_size: DEFAULT_CELL_SIZE,
+            tick_strategy: TickStrategy::Scalar,
+            scan_order: ScanOrder::Shuffled,
+            frozen: false,
+            rule: Rule::conway(),
+            rule_survival: Rule::conway().survival,
+            region_rules: vec![],
+            rule_index: vec![0; size],
+            generation: 0,
+            unchanged_ticks: 0,
+            snapshot_every: 0,
+            snapshots: BTreeMap::new(),
+            max_population: 0,
+            min_population: 0,
+            recording: None,
+        }
+    }
+
     fn get_index(&self, row: u32, column: u32) -> usize {
-        (row * WIDTH + column) as usize
+        (row * self.width + column) as usize
+    }
+
+    /// Index of a cell, or `None` if it's outside the board.
+    fn checked_index(&self, row: u32, column: u32) -> Option<usize> {
+        if row < self.height && column < self.width {
+            Some(self.get_index(row, column))
+        } else {
+            None
+        }
+    }
+
+    /// Storage index of a cell given in coordinates relative to the origin.
+    fn view_index(&self, row: u32, column: u32) -> usize {
+        self.get_index(
+            (row + self.origin.0) % self.height,
+            (column + self.origin.1) % self.width,
+        )
+    }
+
+    /// Storage indices of all cells, row by row starting at the origin.
+    fn view_order(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.height)
+            .flat_map(move |row| (0..self.width).map(move |column| self.view_index(row, column)))
+    }
+
+    /// Flat index relative to the origin of a cell given by its storage index.
+    fn index_in_view(&self, idx: usize) -> usize {
+        let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+        let row = (row + self.height - self.origin.0) % self.height;
+        let column = (column + self.width - self.origin.1) % self.width;
+        self.get_index(row, column)
     }
 
     fn live_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        match self.boundary {
+            Boundary::Toroidal => {}
+            Boundary::Dead => return self.bounded_neighbor_count(row, column),
+            Boundary::Mobius => {
+                return self
+                    .neighbors(row, column)
+                    .into_iter()
+                    .map(|(r, c)| self.cells[self.get_index(r, c)] as u8)
+                    .sum()
+            }
+        }
+
         let mut count = 0;
 
-        let north = if row == 0 { HEIGHT - 1 } else { row - 1 };
+        let north = if row == 0 { self.height - 1 } else { row - 1 };
 
-        let south = if row == HEIGHT - 1 { 0 } else { row + 1 };
+        let south = if row == self.height - 1 { 0 } else { row + 1 };
 
-        let west = if column == 0 { WIDTH - 1 } else { column - 1 };
+        let west = if column == 0 {
+            self.width - 1
+        } else {
+            column - 1
+        };
 
-        let east = if column == WIDTH - 1 { 0 } else { column + 1 };
+        let east = if column == self.width - 1 {
+            0
+        } else {
+            column + 1
+        };
 
         let nw = self.get_index(north, west);
         count += self.cells[nw] as u8;
@@ -114,147 +661,2707 @@ impl Universe {
         count
     }
 
+    fn bounded_neighbor_count(&self, row: u32, column: u32) -> u8 {
+        let mut count = 0;
+        for r in row.saturating_sub(1)..=(row + 1).min(self.height - 1) {
+            for c in column.saturating_sub(1)..=(column + 1).min(self.width - 1) {
+                if (r, c) != (row, column) {
+                    count += self.cells[self.get_index(r, c)] as u8;
+                }
+            }
+        }
+        count
+    }
+
+    /// Coordinates of the eight neighbors of a cell, respecting the boundary mode.
+    fn neighbors(&self, row: u32, column: u32) -> Vec<(u32, u32)> {
+        let mut neighbors = Vec::with_capacity(8);
+        for dr in [-1i64, 0, 1] {
+            for dc in [-1i64, 0, 1] {
+                if (dr, dc) == (0, 0) {
+                    continue;
+                }
+                let (r, c) = (row as i64 + dr, column as i64 + dc);
+                neighbors.extend(self.boundary.resolve(r, c, self.height, self.width));
+            }
+        }
+        neighbors
+    }
+
+    /// Whether any live neighbor of the cell is reached across a wrapping seam.
+    fn has_wrapped_live_neighbor(&self, row: u32, column: u32) -> bool {
+        let (h, w) = (self.height as i64, self.width as i64);
+        for dr in [-1i64, 0, 1] {
+            for dc in [-1i64, 0, 1] {
+                let (r, c) = (row as i64 + dr, column as i64 + dc);
+                if (0..h).contains(&r) && (0..w).contains(&c) {
+                    continue;
+                }
+                let wrapped = self.boundary.resolve(r, c, self.height, self.width);
+                if wrapped.is_some_and(|(r, c)| self.cells[self.get_index(r, c)] == Cell::Alive) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Sizes of 8-connected groups of live cells.
+    fn cluster_sizes(&self) -> Vec<u32> {
+        let live: Vec<bool> = self.cells.iter().map(|c| *c == Cell::Alive).collect();
+        self.components(&live)
+            .iter()
+            .map(|component| component.len() as u32)
+            .collect()
+    }
+
+    /// Flat indices of each 8-connected group of marked cells.
+    fn components(&self, marked: &[bool]) -> Vec<Vec<usize>> {
+        let mut visited = vec![false; self.cells.len()];
+        let mut components = vec![];
+        let mut stack = vec![];
+        for start in 0..self.cells.len() {
+            if visited[start] || !marked[start] {
+                continue;
+            }
+            visited[start] = true;
+            stack.push(start);
+            let mut component = vec![];
+            while let Some(idx) = stack.pop() {
+                component.push(idx);
+                let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+                for (r, c) in self.neighbors(row, column) {
+                    let i = self.get_index(r, c);
+                    if !visited[i] && marked[i] {
+                        visited[i] = true;
+                        stack.push(i);
+                    }
+                }
+            }
+            components.push(component);
+        }
+        components
+    }
+
+    /// Indices of cells changed by the last tick.
+    fn changed_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.diff[..self.diff_count]
+            .iter()
+            .map(|i| (*i & !WRAP_DIFF_FLAG) as usize)
+    }
+
     /// Get the dead and alive values of the entire universe.
     pub fn get_cells(&self) -> &[Cell] {
         &self.cells
     }
 
+    /// Entries of the diff for the last tick, as read through `diff()`.
+    pub fn get_diff(&self) -> &[i32] {
+        &self.diff[..self.diff_count]
+    }
+
+    /// RGBA pixels, one per cell, coloring live cells by their neighbor count
+    /// with `0xRRGGBBAA` palette entries. Dead cells are transparent black.
+    pub fn render_rgba_by_neighbors(&self, palette: &[u32; 9]) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.cells.len() * 4);
+        for idx in self.view_order() {
+            let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+            let color = match self.cells[idx] {
+                Cell::Alive => palette[self.live_neighbor_count(row, column) as usize],
+                Cell::Dead => 0,
+            };
+            pixels.extend_from_slice(&color.to_be_bytes());
+        }
+        pixels
+    }
+
+    /// Changed cells of the last tick, or `DiffResult::Full` when more than
+    /// `threshold` cells changed.
+    pub fn diff_or_full(&self, threshold: usize) -> DiffResult {
+        if self.diff_count > threshold {
+            return DiffResult::Full;
+        }
+        DiffResult::Changes(
+            self.changed_indices()
+                .map(|i| self.index_in_view(i) as u32)
+                .collect(),
+        )
+    }
+
+    /// RGBA pixels, one per cell, coloring live cells between the `young` and `old`
+    /// `0xRRGGBBAA` colors by their age, reaching `old` at `cap` generations.
+    /// A `cap` of 0 renders every live cell `old`. Dead cells are transparent black.
+    pub fn render_age_rgba(&self, young: u32, old: u32, cap: u16) -> Vec<u8> {
+        let (young, old) = (young.to_be_bytes(), old.to_be_bytes());
+        let mut pixels = Vec::with_capacity(self.cells.len() * 4);
+        for idx in self.view_order() {
+            let (cell, age) = (&self.cells[idx], &self.ages[idx]);
+            if *cell == Cell::Dead {
+                pixels.extend_from_slice(&[0; 4]);
+                continue;
+            }
+            let (t, span) = match cap {
+                0 => (1, 1),
+                _ => ((*age).min(cap) as i32, cap as i32),
+            };
+            for (y, o) in young.iter().zip(&old) {
+                let (y, o) = (*y as i32, *o as i32);
+                pixels.push((y + (o - y) * t / span) as u8);
+            }
+        }
+        pixels
+    }
+
+    /// Cells as nested rows of 0 for dead and 1 for alive, starting at the origin.
+    pub fn to_rows(&self) -> Vec<Vec<u8>> {
+        (0..self.height)
+            .map(|row| {
+                (0..self.width)
+                    .map(|column| self.cells[self.view_index(row, column)] as u8)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// State of a cell given relative to the origin.
+    pub fn get_cell(&self, row: u32, column: u32) -> Cell {
+        self.cells[self.view_index(row, column)]
+    }
+
     /// Set cells to be alive in a universe by passing the row and column
     /// of each cell as an array.
     pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        if self.frozen {
+            return;
+        }
+        let before = self.edit_start();
         for (row, col) in cells.iter().cloned() {
             let idx = self.get_index(row, col);
             self.cells[idx] = Cell::Alive;
         }
+        self.record_edit(before);
     }
-}
 
-#[wasm_bindgen]
-impl Universe {
-    pub fn tick(&mut self) {
-        self.diff.fill(-1);
-        let mut diff_index: usize = 0;
-        for row in 0..HEIGHT {
-            for col in 0..WIDTH {
-                let idx = self.get_index(row, col);
-                let cell = self.cells[idx];
-                let live_neighbors = self.live_neighbor_count(row, col);
-                let next_cell = match (cell, live_neighbors) {
-                    (Cell::Alive, x) if x < 2 || x > 3 => Cell::Dead,
-                    (Cell::Alive, 2) | (_, 3) => Cell::Alive,
-                    (x, _) => x,
-                };
+    /// Extract a region into a pattern, wrapping around the edges.
+    pub fn extract_pattern(
+        &self,
+        row: u32,
+        column: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Pattern, LifeError> {
+        let mut alive_cells = vec![];
+        for r in 0..height {
+            for c in 0..width {
+                let idx = self.get_index((row + r) % self.height, (column + c) % self.width);
+                if self.cells[idx] == Cell::Alive {
+                    alive_cells.push((r, c));
+                }
+            }
+        }
+        if alive_cells.is_empty() {
+            return Err(LifeError::EmptyPattern);
+        }
+        Ok(Pattern {
+            alive_cells,
+            width,
+            height,
+        })
+    }
 
-                match (cell, next_cell) {
-                    (a, b) if a != b => {
-                        self.diff[diff_index] = idx as i32;
-                        diff_index += 1;
-                    }
-                    _ => {}
+    /// Top left corners, in row-major order, of every place where the cells of the board
+    /// match the pattern's bounding box exactly. Matches may wrap around the edges
+    /// on a toroidal board; on other boundaries they must lie inside it.
+    pub fn find_pattern(&self, schema: &str) -> Result<Vec<(u32, u32)>, LifeError> {
+        let pattern: Pattern = schema.parse()?;
+        self.check_fits(&pattern)?;
+        let mut alive = vec![false; (pattern.width * pattern.height) as usize];
+        for (x, y) in &pattern.alive_cells {
+            alive[(x * pattern.width + y) as usize] = true;
+        }
+        let (rows, columns) = if self.boundary == Boundary::Toroidal {
+            (self.height, self.width)
+        } else {
+            (
+                self.height - pattern.height + 1,
+                self.width - pattern.width + 1,
+            )
+        };
+        let mut found = vec![];
+        for row in 0..rows {
+            for column in 0..columns {
+                let matches = (0..pattern.height).all(|x| {
+                    (0..pattern.width).all(|y| {
+                        let i = self.get_index((row + x) % self.height, (column + y) % self.width);
+                        (self.cells[i] == Cell::Alive) == alive[(x * pattern.width + y) as usize]
+                    })
+                });
+                if matches {
+                    found.push((row, column));
                 }
+            }
+        }
+        Ok(found)
+    }
 
-                self.buffer[idx] = next_cell;
+    /// Extract all live cells into the smallest possible pattern.
+    /// On a toroidal board the pattern may span the wrap seam,
+    /// so structures sitting on the edge are extracted compactly.
+    pub fn extract_wrapped(&self) -> Result<Pattern, LifeError> {
+        let mut occupied_rows = vec![false; self.height as usize];
+        let mut occupied_columns = vec![false; self.width as usize];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if *cell == Cell::Alive {
+                occupied_rows[i / self.width as usize] = true;
+                occupied_columns[i % self.width as usize] = true;
             }
         }
-        swap(&mut self.cells, &mut self.buffer);
+        let wrap = self.boundary == Boundary::Toroidal;
+        let (Some((row, height)), Some((column, width))) = (
+            occupied_span(&occupied_rows, wrap),
+            occupied_span(&occupied_columns, wrap),
+        ) else {
+            return Err(LifeError::EmptyPattern);
+        };
+        self.extract_pattern(row, column, width, height)
     }
 
-    pub fn diff(&self) -> *const i32 {
-        self.diff.as_ptr()
+    /// Bounding box of live cells as `(row, column, width, height)`,
+    /// ignoring the wrap seam, or `None` when all cells are dead.
+    pub fn live_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        let mut occupied_rows = vec![false; self.height as usize];
+        let mut occupied_columns = vec![false; self.width as usize];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if *cell == Cell::Alive {
+                occupied_rows[i / self.width as usize] = true;
+                occupied_columns[i % self.width as usize] = true;
+            }
+        }
+        let (row, height) = occupied_span(&occupied_rows, false)?;
+        let (column, width) = occupied_span(&occupied_columns, false)?;
+        Some((row, column, width, height))
     }
 
-    pub fn width(&self) -> u32 {
-        WIDTH
+    /// Extract all live cells into a pattern of their bounding box, ignoring the wrap seam.
+    /// An empty board gives an empty 0x0 pattern.
+    pub fn extract_all(&self) -> Pattern {
+        match self.live_bounds() {
+            Some((row, column, width, height)) => self
+                .extract_pattern(row, column, width, height)
+                .expect("live bounds are inside the grid"),
+            None => Pattern {
+                alive_cells: vec![],
+                width: 0,
+                height: 0,
+            },
+        }
     }
 
-    pub fn height(&self) -> u32 {
-        HEIGHT
+    /// Look for a configuration that ticks into the current board by trying
+    /// up to `max_attempts` candidates in order. Only boards of at most 64 cells are searched.
+    /// Returns live cells of the predecessor, or `None` if none was found,
+    /// which suggests the board may be a Garden of Eden.
+    pub fn find_predecessor(&self, max_attempts: u32) -> Option<Vec<(u32, u32)>> {
+        let size = self.cells.len();
+        if size > 64 {
+            return None;
+        }
+        let mut scratch = self.scratch();
+        let candidates = if size == 64 {
+            u64::MAX
+        } else {
+            (1u64 << size) - 1
+        };
+        for bits in (0..=candidates).take(max_attempts as usize) {
+            for (i, cell) in scratch.cells.iter_mut().enumerate() {
+                *cell = if bits >> i & 1 == 1 {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+            }
+            scratch.tick_no_diff();
+            if scratch.cells == self.cells {
+                let predecessor = (0..size)
+                    .filter(|i| bits >> i & 1 == 1)
+                    .map(|i| (i as u32 / self.width, i as u32 % self.width))
+                    .collect();
+                return Some(predecessor);
+            }
+        }
+        None
     }
 
-    pub fn new() -> Universe {
-        utils::set_panic_hook();
+    /// Whether any configuration ticks into the current board, found by trying all of them.
+    /// Only boards of at most `PREDECESSOR_SEARCH_LIMIT` cells are supported.
+    pub fn has_predecessor(&self) -> Result<bool, LifeError> {
+        if self.cells.len() > PREDECESSOR_SEARCH_LIMIT {
+            return Err(LifeError::InvalidArgument(format!(
+                "Exhaustive predecessor search supports at most {} cells, the board has {}",
+                PREDECESSOR_SEARCH_LIMIT,
+                self.cells.len()
+            )));
+        }
+        Ok(self.find_predecessor(u32::MAX).is_some())
+    }
 
-        let mut u = Universe {
-            cells: [Cell::Dead; SIZE],
-            buffer: [Cell::Dead; SIZE],
-            diff: [-1; SIZE],
-        };
-        u.randomize();
-        u
+    /// Smallest period, up to `max_period`, with which the state of a single cell repeats
+    /// over the next `2 * max_period` generations, or `None` for cells outside the board.
+    /// The board itself is left untouched.
+    pub fn cell_period(&self, row: u32, column: u32, max_period: u32) -> Option<u32> {
+        let idx = self.checked_index(row, column)?;
+        let mut scratch = self.scratch();
+        let mut states = vec![self.cells[idx]];
+        for _ in 0..2 * max_period {
+            scratch.tick_no_diff();
+            states.push(scratch.cells[idx]);
+        }
+        (1..=max_period as usize)
+            .find(|p| (0..states.len() - p).all(|t| states[t] == states[t + p]))
+            .map(|p| p as u32)
     }
 
-    pub fn randomize(&mut self) {
-        for i in 0..SIZE {
-            self.cells[i] = if random() { Cell::Dead } else { Cell::Alive };
+    /// Number of ticks, up to `max`, after which all cells are dead, or `None` if some
+    /// survive that long. An empty board gives `Some(0)`. The board itself is left untouched.
+    pub fn ticks_until_extinct(&self, max: u32) -> Option<u32> {
+        let mut scratch = self.scratch();
+        let mut population = scratch.population();
+        for ticks in 0..max {
+            if population == 0 {
+                return Some(ticks);
+            }
+            population = scratch.tick_population();
+        }
+        if population == 0 {
+            Some(max)
+        } else {
+            None
         }
     }
 
-    pub fn clear(&mut self) {
-        for i in 0..SIZE {
-            self.cells[i as usize] = Cell::Dead;
+    /// Split cells by watching the next `period` generations into those that change
+    /// at least once and live ones that never do, as flat indices.
+    /// The board itself is left untouched.
+    pub fn partition_dynamic(&self, period: u32) -> (Vec<u32>, Vec<u32>) {
+        let mut changed = vec![false; self.cells.len()];
+        let mut scratch = self.scratch();
+        for _ in 0..period {
+            scratch.tick();
+            for i in scratch.changed_indices() {
+                changed[i] = true;
+            }
         }
+        let oscillating = (0..self.cells.len()).filter(|i| changed[*i]);
+        let still = (0..self.cells.len()).filter(|i| !changed[*i] && self.cells[*i] == Cell::Alive);
+        (
+            oscillating.map(|i| i as u32).collect(),
+            still.map(|i| i as u32).collect(),
+        )
     }
 
-    pub fn cells(&self) -> *const Cell {
-        self.cells.as_ptr()
+    /// Board of the same size and rules with the same cells, for looking ahead.
+    fn scratch(&self) -> Universe {
+        let mut scratch = Universe::empty(self.width, self.height);
+        scratch.rule = self.rule;
+        scratch.region_rules = self.region_rules.clone();
+        scratch.rule_index.copy_from_slice(&self.rule_index);
+        scratch.boundary = self.boundary;
+        scratch.cells.copy_from_slice(&self.cells);
+        scratch
     }
 
-    pub fn toggle_cell(&mut self, row: u32, column: u32) {
+    /// Create an empty universe of the given size with only the given cells alive.
+    /// Zero sizes are raised to one cell.
+    pub fn from_cells(width: u32, height: u32, alive: &[(u32, u32)]) -> Universe {
+        let mut universe = Universe::empty(width.max(1), height.max(1));
+        universe.set_cells(alive);
+        universe.reset_stats();
+        universe
+    }
+
+    /// State of the cell in the next generation.
+    fn next_state(&self, row: u32, column: u32) -> Cell {
         let idx = self.get_index(row, column);
-        self.cells[idx].toggle();
+        self.apply_rule(idx, self.live_neighbor_count(row, column))
     }
 
-    fn insert_pattern(&mut self, pattern: &Pattern, row: u32, column: u32) {
-        let center = (pattern.width / 2, pattern.height / 2);
-        let row = ((row - center.0) + WIDTH) % WIDTH;
-        let column = ((column - center.1) + HEIGHT) % HEIGHT;
-        for x in 0..pattern.width {
-            for y in 0..pattern.height {
-                let x = (row + x) % WIDTH;
-                let y = (column + y) % HEIGHT;
-                let i = self.get_index(x, y);
-                self.cells[i] = Cell::Dead;
-            }
-        }
-        for (x, y) in &pattern.alive_cells {
-            let x = (row + x) % WIDTH;
-            let y = (column + y) % HEIGHT;
-            let i = self.get_index(x, y);
-            self.cells[i] = Cell::Alive;
+    /// State of a cell with the given number of live neighbors in the next generation,
+    /// under the rule of the region it's in.
+    fn apply_rule(&self, idx: usize, live_neighbors: u8) -> Cell {
+        let rule = match self.rule_index[idx] {
+            0 => &self.rule,
+            i => &self.region_rules[i as usize - 1],
+        };
+        let table = match self.cells[idx] {
+            Cell::Alive => &rule.survival,
+            Cell::Dead => &rule.birth,
+        };
+        if table[live_neighbors as usize] {
+            Cell::Alive
+        } else {
+            Cell::Dead
         }
     }
 
-    pub fn insert_glider(&mut self, row: u32, column: u32) {
-        let glider = "!Name: Glider
-!Author: Richard K. Guy
-!The smallest, most common, and first discovered spaceship.
-!www.conwaylife.com/wiki/index.php?title=Glider
-.O
-..O
-OOO"
-        .parse()
-        .unwrap();
-        self.insert_pattern(&glider, row, column);
+    /// Live neighbor counts of all cells, computed a row at a time by adding
+    /// vertically shifted copies of the rows, then horizontally shifted copies of those sums.
+    /// Not valid for the Möbius boundary.
+    fn row_sum_neighbor_counts(&self) -> Vec<u8> {
+        let (w, h) = (self.width as usize, self.height as usize);
+        let wrap = self.boundary == Boundary::Toroidal;
+        let dead = vec![Cell::Dead; w];
+        let row = |r: usize| &self.cells[r * w..(r + 1) * w];
+        let mut vertical = vec![0u8; w];
+        let mut counts = vec![0u8; w * h];
+        for r in 0..h {
+            let above = match r {
+                0 if wrap => row(h - 1),
+                0 => &dead,
+                _ => row(r - 1),
+            };
+            let below = match r + 1 {
+                n if n < h => row(n),
+                _ if wrap => row(0),
+                _ => &dead,
+            };
+            for (((v, a), c), b) in vertical.iter_mut().zip(above).zip(row(r)).zip(below) {
+                *v = *a as u8 + *c as u8 + *b as u8;
+            }
+            let out = &mut counts[r * w..(r + 1) * w];
+            out.copy_from_slice(&vertical);
+            for (o, v) in out[1..].iter_mut().zip(&vertical[..w - 1]) {
+                *o += v;
+            }
+            for (o, v) in out[..w - 1].iter_mut().zip(&vertical[1..]) {
+                *o += v;
+            }
+            if wrap {
+                out[0] += vertical[w - 1];
+                out[w - 1] += vertical[0];
+            }
+            for (o, c) in out.iter_mut().zip(row(r)) {
+                *o -= *c as u8;
+            }
+        }
+        counts
     }
 
-    pub fn insert_pulsar(&mut self, row: u32, column: u32) {
-        let pulsar: Pattern = "!Name: Pulsar
-!Author: John Conway
-!Despite its size, this is the fourth most common oscillator (and by far the most common of period greater than 2).
-!www.conwaylife.com/wiki/index.php?title=Pulsar
-..OOO...OOO
-
-O....O.O....O
-O....O.O....O
-O....O.O....O
-..OOO...OOO
+    /// Color of a cell born at the given position: the majority color of its parents,
+    /// or in QuadLife, when three parents all differ, the color none of them has.
+    /// Ties between equally common colors are broken by the `ColorTiebreak` setting.
+    fn birth_color(&self, row: u32, column: u32) -> u8 {
+        let mut counts = [0u8; 4];
+        for (r, c) in self.neighbors(row, column) {
+            let i = self.get_index(r, c);
+            if self.cells[i] == Cell::Alive {
+                counts[self.colors[i] as usize] += 1;
+            }
+        }
+        let colors = 0..self.mode.colors();
+        let most = colors
+            .clone()
+            .map(|c| counts[c as usize])
+            .max()
+            .unwrap_or(0);
+        let parents: u8 = counts.iter().sum();
+        if self.mode == Mode::QuadLife && parents == 3 && most == 1 {
+            if let Some(missing) = colors.clone().find(|c| counts[*c as usize] == 0) {
+                return missing;
+            }
+        }
+        let mut tied = colors.filter(|c| counts[*c as usize] == most);
+        match self.color_tiebreak {
+            ColorTiebreak::Lowest => tied.next(),
+            ColorTiebreak::Highest => tied.next_back(),
+        }
+        .unwrap_or(0)
+    }
+
+    /// List a changed cell in the diff unless it's already there.
+    /// Returns whether it was added.
+    fn record_change(&mut self, idx: usize) -> bool {
+        if self.in_diff[idx] {
+            return false;
+        }
+        self.in_diff[idx] = true;
+        let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+        let on_border =
+            row == 0 || column == 0 || row + 1 == self.height || column + 1 == self.width;
+        self.diff[self.diff_count] = if self.wrap_diff && on_border {
+            idx as i32 | WRAP_DIFF_FLAG
+        } else {
+            idx as i32
+        };
+        self.diff_count += 1;
+        true
+    }
+
+    fn step(&mut self, record_diff: bool) -> u32 {
+        self.step_region(record_diff, 0..self.height, 0..self.width)
+    }
+
+    /// Flat indices of all cells in the scan order, shuffled with the seed if needed.
+    fn scan_indices(&self, seed: u64) -> Vec<usize> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        match self.scan_order {
+            ScanOrder::Shuffled => {
+                let mut rng = Rng::new(seed);
+                let mut order: Vec<usize> = (0..self.cells.len()).collect();
+                for i in (1..order.len()).rev() {
+                    order.swap(i, rng.next_below(i as u32 + 1) as usize);
+                }
+                order
+            }
+            ScanOrder::RowMajor => (0..self.cells.len()).collect(),
+            ScanOrder::ColumnMajor => (0..width)
+                .flat_map(|column| (0..height).map(move |row| row * width + column))
+                .collect(),
+            ScanOrder::BoustrophedonRow => (0..height)
+                .flat_map(|row| {
+                    (0..width).map(move |i| {
+                        let column = if row % 2 == 0 { i } else { width - 1 - i };
+                        row * width + column
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    /// Advance one generation, updating only cells inside the given rows and columns.
+    /// Returns the population counted along the way.
+    fn step_region(&mut self, record_diff: bool, rows: Range<u32>, columns: Range<u32>) -> u32 {
+        if self.frozen {
+            return self.population();
+        }
+        if rows == (0..self.height) && columns == (0..self.width) {
+            self.record(|| "tick".to_string());
+        } else {
+            self.record(|| {
+                format!(
+                    "tick_region {} {} {} {}",
+                    rows.start,
+                    columns.start,
+                    columns.len(),
+                    rows.len()
+                )
+            });
+        }
+        if record_diff && !self.diff_accumulate {
+            self.clear_diff();
+        }
+        let counts = match (self.tick_strategy, self.boundary) {
+            (TickStrategy::RowSums, Boundary::Toroidal | Boundary::Dead) => {
+                Some(self.row_sum_neighbor_counts())
+            }
+            _ => None,
+        };
+        let mut population = 0;
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                let cell = self.cells[idx];
+                let mut next_cell = match &counts {
+                    _ if !rows.contains(&row) || !columns.contains(&col) => cell,
+                    Some(counts) => self.apply_rule(idx, counts[idx]),
+                    None => self.next_state(row, col),
+                };
+                if let Some((birth_p, death_p, rng)) = &mut self.noise {
+                    let p = if cell == Cell::Dead {
+                        *birth_p
+                    } else {
+                        *death_p
+                    };
+                    if next_cell != cell && rng.next_f64() >= p {
+                        next_cell = cell;
+                    }
+                }
+
+                if record_diff
+                    && cell != next_cell
+                    && self.record_change(idx)
+                    && self.has_wrapped_live_neighbor(row, col)
+                {
+                    self.wrapped_changes.push(idx as u32);
+                }
+
+                match (cell, next_cell) {
+                    (Cell::Alive, Cell::Dead) => {
+                        self.fade[idx] = 255;
+                        self.tags[idx] = 0;
+                        self.ages[idx] = 0;
+                    }
+                    (Cell::Dead, Cell::Alive) => {
+                        self.fade[idx] = 0;
+                        self.tags[idx] = 0;
+                        self.ages[idx] = 0;
+                    }
+                    (Cell::Alive, Cell::Alive) => {
+                        self.ages[idx] = self.ages[idx].saturating_add(1);
+                    }
+                    _ => {}
+                }
+
+                if self.mode != Mode::Classic {
+                    self.color_buffer[idx] = match (cell, next_cell) {
+                        (_, Cell::Dead) => 0,
+                        (Cell::Dead, Cell::Alive) => self.birth_color(row, col),
+                        (Cell::Alive, Cell::Alive) => self.colors[idx],
+                    };
+                }
+
+                self.buffer[idx] = next_cell;
+                population += next_cell as u32;
+            }
+        }
+        swap(&mut self.cells, &mut self.buffer);
+        if self.mode != Mode::Classic {
+            swap(&mut self.colors, &mut self.color_buffer);
+        }
+        self.finish_generation(population);
+        population
+    }
+
+    /// Update counters after a tick, with the previous generation left in `buffer`.
+    fn finish_generation(&mut self, population: u32) {
+        if self.cells == self.buffer {
+            self.unchanged_ticks += 1;
+        } else {
+            self.unchanged_ticks = 0;
+        }
+        self.generation += 1;
+        self.take_snapshot();
+        self.max_population = self.max_population.max(population);
+        self.min_population = self.min_population.min(population);
+    }
+
+    /// Tick `count` times, rendering each new generation as a grayscale PNG image
+    /// with `scale` pixels per cell side: live cells black, dead ones white.
+    /// `scale` is lowered so a frame has at most 2^22 pixels, about 4 megapixels,
+    /// but never below one pixel per cell.
+    #[cfg(feature = "image-export")]
+    pub fn capture_frames(&mut self, count: u32, scale: u32) -> Vec<Vec<u8>> {
+        let cells = self.width as u64 * self.height as u64;
+        let max_scale = (FRAME_PIXEL_LIMIT / cells).isqrt() as u32;
+        let scale = scale.min(max_scale).max(1);
+        let (width, height) = (self.width * scale, self.height * scale);
+        (0..count)
+            .map(|_| {
+                self.tick();
+                let mut pixels = Vec::with_capacity(width as usize * height as usize);
+                for y in 0..height {
+                    for x in 0..width {
+                        pixels.push(match self.get_cell(y / scale, x / scale) {
+                            Cell::Alive => 0,
+                            Cell::Dead => 255,
+                        });
+                    }
+                }
+                png::encode_gray(width, height, &pixels)
+            })
+            .collect()
+    }
+
+    /// Tick once and return the nanoseconds it took.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tick_timed(&mut self) -> u64 {
+        let start = std::time::Instant::now();
+        self.tick();
+        start.elapsed().as_nanos() as u64
+    }
+
+    /// Tick until the wall-clock budget is exhausted, at least once.
+    /// Returns the number of generations completed.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tick_for(&mut self, duration: std::time::Duration) -> u32 {
+        let start = std::time::Instant::now();
+        let mut generations = 0;
+        loop {
+            self.tick();
+            generations += 1;
+            if start.elapsed() >= duration {
+                return generations;
+            }
+        }
+    }
+}
+
+/// Largest board, in cells, that `has_predecessor` searches exhaustively.
+const PREDECESSOR_SEARCH_LIMIT: usize = 20;
+
+/// Boards larger than this are truncated when displayed.
+const DISPLAY_LIMIT: u32 = 256;
+
+/// Largest number of pixels in a frame rendered by `capture_frames`.
+#[cfg(feature = "image-export")]
+const FRAME_PIXEL_LIMIT: u64 = 1 << 22;
+
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in 0..self.height.min(DISPLAY_LIMIT) {
+            for col in 0..self.width.min(DISPLAY_LIMIT) {
+                let symbol = match self.cells[self.view_index(row, col)] {
+                    Cell::Dead => '.',
+                    Cell::Alive => 'O',
+                };
+                write!(f, "{}", symbol)?;
+            }
+            if self.width > DISPLAY_LIMIT {
+                write!(f, "...")?;
+            }
+            writeln!(f)?;
+        }
+        if self.height > DISPLAY_LIMIT {
+            writeln!(f, "...")?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Universe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl Universe {
+    pub fn tick(&mut self) {
+        self.step(true);
+    }
+
+    /// Tick and return the new population, counted during the tick itself.
+    pub fn tick_population(&mut self) -> u32 {
+        self.step(true)
+    }
+
+    /// Compute the next generation without recording changes,
+    /// for fast-forwarding. `diff()` is stale afterward.
+    pub fn tick_no_diff(&mut self) {
+        self.step(false);
+    }
+
+    /// Preallocate room for `capacity` changed cells per tick, so runs with a known
+    /// activity level don't reallocate. The diff already spans the board; this reserves
+    /// the growing `wrapped_changes` list, up to the number of cells.
+    pub fn reserve_active(&mut self, capacity: usize) {
+        let capacity = capacity.min(self.cells.len());
+        self.wrapped_changes
+            .reserve(capacity.saturating_sub(self.wrapped_changes.len()));
+    }
+
+    /// Tick and report milestones reached on this tick: "extinct" when the last
+    /// cell dies, "stabilized" when a live board stops changing and
+    /// "population-peak" when the population exceeds its previous maximum.
+    pub fn tick_with_events(&mut self) -> Vec<String> {
+        let population = self.population();
+        let generation = self.generation;
+        let peak = self.max_population;
+        let next_population = self.step(true);
+        let mut events = vec![];
+        if population > 0 && next_population == 0 {
+            events.push("extinct".to_string());
+        }
+        // The first unchanged tick in a row, judged by the boards themselves
+        // so it works with an accumulated or stale diff.
+        if self.generation > generation && self.unchanged_ticks == 1 && next_population > 0 {
+            events.push("stabilized".to_string());
+        }
+        if next_population > peak {
+            events.push("population-peak".to_string());
+        }
+        events
+    }
+
+    /// Make each birth and death dictated by the rule happen only with the given
+    /// probability, drawn from a generator seeded with `seed`.
+    /// Probabilities of 1.0 restore the deterministic rule.
+    pub fn set_noise(&mut self, birth_p: f64, death_p: f64, seed: u64) -> Result<(), LifeError> {
+        if !(0.0..=1.0).contains(&birth_p) || !(0.0..=1.0).contains(&death_p) {
+            return Err(LifeError::InvalidArgument(format!(
+                "Probabilities must be between 0 and 1, got {} and {}",
+                birth_p, death_p
+            )));
+        }
+        self.record(|| format!("noise {} {} {}", birth_p, death_p, seed));
+        self.noise = if birth_p == 1.0 && death_p == 1.0 {
+            None
+        } else {
+            Some((birth_p, death_p, Rng::new(seed)))
+        };
+        Ok(())
+    }
+
+    /// Update every cell once, one at a time in the order set by `set_scan_order`,
+    /// so later cells see the already updated state of their neighbors.
+    /// The seed is only used by the `Shuffled` order.
+    /// `wrapped_changes()` is not updated.
+    pub fn tick_async(&mut self, seed: u64) {
+        if self.frozen {
+            return;
+        }
+        self.record(|| format!("tick_async {}", seed));
+        let order = self.scan_indices(seed);
+        if !self.diff_accumulate {
+            self.clear_diff();
+        }
+        self.buffer.copy_from_slice(&self.cells);
+        for idx in order {
+            let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+            let cell = self.cells[idx];
+            let next_cell = self.next_state(row, column);
+            if cell == next_cell {
+                if cell == Cell::Alive {
+                    self.ages[idx] = self.ages[idx].saturating_add(1);
+                }
+                continue;
+            }
+            if self.mode != Mode::Classic {
+                self.colors[idx] = match next_cell {
+                    Cell::Dead => 0,
+                    Cell::Alive => self.birth_color(row, column),
+                };
+            }
+            self.fade[idx] = if next_cell == Cell::Dead { 255 } else { 0 };
+            self.tags[idx] = 0;
+            self.ages[idx] = 0;
+            self.cells[idx] = next_cell;
+        }
+        for idx in 0..self.cells.len() {
+            if self.cells[idx] != self.buffer[idx] {
+                self.record_change(idx);
+            }
+        }
+        self.finish_generation(self.population());
+    }
+
+    pub fn scan_order(&self) -> ScanOrder {
+        self.scan_order
+    }
+
+    /// Choose the order in which `tick_async` visits cells.
+    pub fn set_scan_order(&mut self, order: ScanOrder) {
+        self.record(|| format!("scan_order {:?}", order));
+        self.scan_order = order;
+    }
+
+    pub fn tick_strategy(&self) -> TickStrategy {
+        self.tick_strategy
+    }
+
+    pub fn set_tick_strategy(&mut self, strategy: TickStrategy) {
+        self.tick_strategy = strategy;
+    }
+
+    /// Tick only the cells inside the rectangle; cells outside it are frozen
+    /// but still count as neighbors.
+    pub fn tick_region(&mut self, row: u32, column: u32, width: u32, height: u32) {
+        let rows = row..row.saturating_add(height).min(self.height);
+        let columns = column..column.saturating_add(width).min(self.width);
+        self.step_region(true, rows, columns);
+    }
+
+    /// Number of consecutive ticks, up to now, that didn't change any cell.
+    /// Toggling a cell starts the count over.
+    pub fn generations_since_change(&self) -> u64 {
+        self.unchanged_ticks
+    }
+
+    /// Number of ticks since the last reset.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Remember the board every `every` generations (0 disables it),
+    /// so that `goto_generation` can scrub back in time.
+    /// Edits made between ticks are not tracked by the timeline.
+    pub fn set_snapshot_interval(&mut self, every: u64) {
+        self.snapshot_every = every;
+        self.snapshots.clear();
+        if every != 0 {
+            self.snapshots
+                .insert(self.generation, (self.cells.clone(), self.colors.clone()));
+        }
+    }
+
+    fn take_snapshot(&mut self) {
+        if self.snapshot_every != 0 && self.generation.is_multiple_of(self.snapshot_every) {
+            self.snapshots
+                .insert(self.generation, (self.cells.clone(), self.colors.clone()));
+        }
+    }
+
+    /// Jump to a generation, restoring the nearest earlier snapshot and ticking forward from it.
+    /// `diff()` is stale afterward, so the whole board should be redrawn.
+    pub fn goto_generation(&mut self, gen: u64) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let nearest = self.snapshots.range(..=gen).next_back().map(|(g, _)| *g);
+        match nearest {
+            Some(g) if gen < self.generation || g > self.generation => {
+                let before = self.edit_start();
+                let (cells, colors) = &self.snapshots[&g];
+                self.cells.copy_from_slice(cells);
+                self.colors.copy_from_slice(colors);
+                self.record_edit(before);
+                self.generation = g;
+            }
+            None if gen < self.generation => {
+                return Err(LifeError::InvalidArgument(format!(
+                    "Generation {} is not in the timeline",
+                    gen
+                )));
+            }
+            _ => {}
+        }
+        while self.generation < gen {
+            self.tick();
+        }
+        self.clear_diff();
+        Ok(())
+    }
+
+    /// Tick up to the target generation, sampling the population
+    /// whenever the generation is a multiple of `report_every` (never if it's zero).
+    pub fn run_to(&mut self, target_gen: u64, report_every: u64) -> Vec<u32> {
+        let mut samples = vec![];
+        while self.generation < target_gen {
+            self.tick();
+            if report_every != 0 && self.generation.is_multiple_of(report_every) {
+                samples.push(self.population());
+            }
+        }
+        samples
+    }
+
+    /// Tick `n` times and return the population after each tick.
+    pub fn populations(&mut self, n: u32) -> Vec<u32> {
+        (0..n)
+            .map(|_| {
+                self.tick();
+                self.population()
+            })
+            .collect()
+    }
+
+    pub fn diff(&self) -> *const i32 {
+        self.diff.as_ptr()
+    }
+
+    /// Indices of cells that will change on the next tick, without ticking.
+    pub fn preview_next(&self) -> Vec<i32> {
+        let mut changes = vec![];
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.get_index(row, col);
+                if self.next_state(row, col) != self.cells[idx] {
+                    changes.push(idx as i32);
+                }
+            }
+        }
+        changes
+    }
+
+    /// Cells changed by the last tick that had a live neighbor across the toroidal seam.
+    pub fn wrapped_changes(&self) -> Vec<u32> {
+        self.wrapped_changes.clone()
+    }
+
+    /// Number of cells changed by the last tick.
+    pub fn diff_count(&self) -> u32 {
+        self.diff_count as u32
+    }
+
+    /// Hamming distance between the current and the previous generation:
+    /// the number of cells that differ from `prev_cells`.
+    /// Unlike `diff_count` it's computed from the boards themselves,
+    /// so it also counts edits made since the last tick.
+    pub fn generation_delta(&self) -> u32 {
+        self.cells
+            .iter()
+            .zip(&self.buffer)
+            .filter(|(cell, previous)| cell != previous)
+            .count() as u32
+    }
+
+    /// When on, `tick` appends to the diff instead of replacing it,
+    /// until `clear_diff` is called. Each cell is listed at most once.
+    pub fn set_diff_accumulate(&mut self, on: bool) {
+        self.diff_accumulate = on;
+    }
+
+    /// When on, entries of `diff()` for cells on the border of the board, next to
+    /// the seam where a torus wraps, have bit 30 set (`index | 1 << 30`), so renderers
+    /// can redraw the mirrored edge. Boards must then have fewer than 2^30 cells.
+    /// Other views of the diff list plain indices.
+    pub fn set_wrap_diff(&mut self, on: bool) {
+        self.wrap_diff = on;
+    }
+
+    /// Empty the diff, e.g. after the renderer consumed accumulated changes.
+    pub fn clear_diff(&mut self) {
+        for i in &self.diff[..self.diff_count] {
+            self.in_diff[(*i & !WRAP_DIFF_FLAG) as usize] = false;
+        }
+        self.diff[..self.diff_count].fill(-1);
+        self.diff_count = 0;
+        self.wrapped_changes.clear();
+    }
+
+    /// Copy of exactly the changed indices of the last tick, relative to the origin.
+    #[cfg(target_arch = "wasm32")]
+    pub fn diff_view(&self) -> js_sys::Int32Array {
+        js_sys::Int32Array::from(&self.diff_in_view()[..])
+    }
+
+    /// Copy of exactly the changed indices of the last tick, relative to the origin.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn diff_view(&self) -> Vec<i32> {
+        self.diff_in_view()
+    }
+
+    fn diff_in_view(&self) -> Vec<i32> {
+        self.changed_indices()
+            .map(|i| self.index_in_view(i) as i32)
+            .collect()
+    }
+
+    /// Size of a cell in pixels that renderers should use. Doesn't affect the simulation.
+    pub fn cell_size(&self) -> u32 {
+        self.cell_size
+    }
+
+    pub fn set_cell_size(&mut self, px: u32) {
+        self.cell_size = px;
+    }
+
+    /// Scroll views of the board so the given cell is shown at the top left corner.
+    /// Cells don't move: `cells()`, `get_cells`, `diff()`, `get_diff`, `seam_cells`,
+    /// `wrapped_changes` and `coords_to_index` keep using storage order, while
+    /// cell lookups, diff views, renderings and `index_to_coords` are offset.
+    pub fn set_origin(&mut self, row: u32, column: u32) {
+        self.origin = (row % self.height.max(1), column % self.width.max(1));
+    }
+
+    /// Changes of the last tick, births stored as `index`
+    /// and deaths as `-(index + 1)`.
+    pub fn diff_typed(&self) -> Vec<i32> {
+        self.changed_indices()
+            .map(|i| {
+                let view = self.index_in_view(i) as i32;
+                match self.cells[i] {
+                    Cell::Alive => view,
+                    Cell::Dead => -(view + 1),
+                }
+            })
+            .collect()
+    }
+
+    /// Normalized `x, y` pairs of live cells' top left corners,
+    /// for use as an instance attribute buffer.
+    pub fn alive_instance_data(&self) -> Vec<f32> {
+        let mut data = vec![];
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.get_cell(row, col) == Cell::Alive {
+                    data.push(col as f32 / self.width as f32);
+                    data.push(row as f32 / self.height as f32);
+                }
+            }
+        }
+        data
+    }
+
+    /// SVG document with a square of `cell_px` pixels per live cell.
+    pub fn to_svg(&self, cell_px: u32) -> String {
+        let (width, height) = (self.width * cell_px, self.height * cell_px);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" style=\"background:#FFFFFF\">\n",
+            w = width,
+            h = height
+        );
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.get_cell(row, col) == Cell::Alive {
+                    svg.push_str(&format!(
+                        "<rect x=\"{}\" y=\"{}\" width=\"{p}\" height=\"{p}\" fill=\"#000000\"/>\n",
+                        col * cell_px,
+                        row * cell_px,
+                        p = cell_px
+                    ));
+                }
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Cells as a JS array of rows, each an array of 0 for dead and 1 for alive.
+    #[cfg(target_arch = "wasm32")]
+    pub fn to_2d_array(&self) -> js_sys::Array {
+        self.to_rows()
+            .into_iter()
+            .map(|row| js_sys::Array::from(&js_sys::Uint8Array::from(&row[..])))
+            .collect()
+    }
+
+    /// `[row, column]` relative to the origin of the cell at the given flat index,
+    /// as used by `cells()` and `diff()`.
+    pub fn index_to_coords(&self, index: u32) -> Vec<u32> {
+        let index = self.index_in_view(index as usize % self.cells.len()) as u32;
+        vec![index / self.width, index % self.width]
+    }
+
+    /// Flat index, as used by `cells()` and `diff()`, of the cell
+    /// at the given coordinates relative to the origin.
+    pub fn coords_to_index(&self, row: u32, column: u32) -> u32 {
+        self.view_index(row % self.height, column % self.width) as u32
+    }
+
+    /// Flat indices of the border cells, where the board wraps around, in row-major order.
+    pub fn seam_cells(&self) -> Vec<u32> {
+        let (last_row, last_column) = (self.height.saturating_sub(1), self.width.saturating_sub(1));
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |column| (row, column)))
+            .filter(|(row, column)| {
+                *row == 0 || *row == last_row || *column == 0 || *column == last_column
+            })
+            .map(|(row, column)| self.get_index(row, column) as u32)
+            .collect()
+    }
+
+    /// Chebyshev distance between two cells, wrapping around both axes
+    /// regardless of the boundary mode.
+    pub fn toroidal_distance(&self, r1: u32, c1: u32, r2: u32, c2: u32) -> u32 {
+        let wrapped = |a: u32, b: u32, size: u32| {
+            let d = (a % size).abs_diff(b % size);
+            d.min(size - d)
+        };
+        wrapped(r1, r2, self.height).max(wrapped(c1, c2, self.width))
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Set the width of the universe. Resets all cells to the dead state.
+    /// A width of 0 is ignored.
+    pub fn set_width(&mut self, width: u32) {
+        if self.frozen {
+            return;
+        }
+        self.resize(width, self.height);
+    }
+
+    /// Set the height of the universe. Resets all cells to the dead state.
+    /// A height of 0 is ignored.
+    pub fn set_height(&mut self, height: u32) {
+        if self.frozen {
+            return;
+        }
+        self.resize(self.width, height);
+    }
+
+    /// Replace the board with an empty one of the given size, keeping the settings.
+    /// Empty sizes and ones with more cells than fit into `u32` are ignored.
+    fn resize(&mut self, width: u32, height: u32) {
+        if width == 0 || height == 0 || cell_count(width, height).is_none() {
+            return;
+        }
+        let resized = Universe {
+            mode: self.mode,
+            color_tiebreak: self.color_tiebreak,
+            rng: self.rng.clone(),
+            noise: self.noise.clone(),
+            boundary: self.boundary,
+            rule: self.rule,
+            rule_survival: self.rule_survival,
+            snapshot_every: self.snapshot_every,
+            diff_accumulate: self.diff_accumulate,
+            wrap_diff: self.wrap_diff,
+            cell_size: self.cell_size,
+            tick_strategy: self.tick_strategy,
+            scan_order: self.scan_order,
+            ..Universe::empty(width, height)
+        };
+        *self = resized;
+        self.reset_stats();
+    }
+
+    /// Approximate heap bytes used by the cell buffers, diff and snapshots.
+    pub fn memory_footprint(&self) -> usize {
+        let per_board = |cells: &Vec<Cell>, colors: &Vec<u8>| {
+            cells.capacity() * size_of::<Cell>() + colors.capacity()
+        };
+        per_board(&self.cells, &self.colors)
+            + per_board(&self.buffer, &self.color_buffer)
+            + self.diff.capacity() * size_of::<i32>()
+            + self.in_diff.capacity()
+            + self.wrapped_changes.capacity() * size_of::<u32>()
+            + self.fade.capacity()
+            + self.tags.capacity()
+            + self.ages.capacity() * size_of::<u16>()
+            + self.rule_index.capacity()
+            + self
+                .snapshots
+                .values()
+                .map(|(cells, colors)| per_board(cells, colors))
+                .sum::<usize>()
+    }
+
+    pub fn new() -> Universe {
+        utils::set_panic_hook();
+        Universe::new_without_hook()
+    }
+
+    /// Same as `new`, but leaves panic handling to the embedder.
+    pub fn new_without_hook() -> Universe {
+        let mut u = Universe::empty(DEFAULT_WIDTH, DEFAULT_HEIGHT);
+        u.randomize();
+        u
+    }
+
+    /// Create a universe sized to the PNG image,
+    /// with pixels darker than `threshold` alive.
+    #[cfg(feature = "image-import")]
+    pub fn from_png(bytes: &[u8], threshold: u8) -> Result<Universe, LifeError> {
+        let (width, height, luma) = png::decode_luma(bytes).map_err(LifeError::InvalidData)?;
+        if width == 0 || height == 0 || cell_count(width, height).is_none() {
+            return Err(LifeError::InvalidData(format!(
+                "Invalid image size: {}x{}",
+                width, height
+            )));
+        }
+        let mut universe = Universe::empty(width, height);
+        for (cell, l) in universe.cells.iter_mut().zip(luma) {
+            if l < threshold {
+                *cell = Cell::Alive;
+            }
+        }
+        universe.reset_stats();
+        Ok(universe)
+    }
+
+    /// Encode the board as `width`, `height` (both little endian u32),
+    /// cells packed one bit each in row-major order
+    /// and a CRC32 checksum of everything before it.
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        for chunk in self.cells.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (i, c)| byte | (*c as u8) << i);
+            bytes.push(byte);
+        }
+        let checksum = utils::crc32(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Restore a board encoded by `serialize`.
+    pub fn deserialize(bytes: &[u8]) -> Result<Universe, LifeError> {
+        if bytes.len() < 12 {
+            return Err(LifeError::InvalidData(format!(
+                "Serialized universe is too short: {} bytes",
+                bytes.len()
+            )));
+        }
+        let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+        if utils::crc32(payload).to_le_bytes() != checksum {
+            return Err(LifeError::InvalidData(
+                "Serialized universe is corrupted: checksum mismatch".to_string(),
+            ));
+        }
+        let width = u32::from_le_bytes(payload[0..4].try_into().unwrap());
+        let height = u32::from_le_bytes(payload[4..8].try_into().unwrap());
+        if width == 0 || height == 0 {
+            return Err(LifeError::InvalidData(format!(
+                "Invalid universe size: {}x{}",
+                width, height
+            )));
+        }
+        let size = cell_count(width, height).ok_or_else(|| {
+            LifeError::InvalidData(format!(
+                "Universe of {}x{} cells is too large",
+                width, height
+            ))
+        })?;
+        let packed = &payload[8..];
+        if packed.len() != size.div_ceil(8) {
+            return Err(LifeError::InvalidData(format!(
+                "Expected {} bytes of cells, got {}",
+                size.div_ceil(8),
+                packed.len()
+            )));
+        }
+        let mut universe = Universe::empty(width, height);
+        for i in 0..size {
+            if packed[i / 8] >> (i % 8) & 1 == 1 {
+                universe.cells[i] = Cell::Alive;
+            }
+        }
+        universe.reset_stats();
+        Ok(universe)
+    }
+
+    /// Serialized board as URL-safe base64.
+    pub fn to_base64(&self) -> String {
+        utils::base64_encode(&self.serialize())
+    }
+
+    /// Restore a board encoded by `to_base64`.
+    pub fn from_base64(encoded: &str) -> Result<Universe, LifeError> {
+        Universe::deserialize(&utils::base64_decode(encoded)?)
+    }
+
+    pub fn randomize(&mut self) {
+        if self.frozen {
+            return;
+        }
+        let seed = self.rng.state();
+        self.record(|| format!("randomize {}", seed));
+        for cell in self.cells.iter_mut() {
+            *cell = if self.rng.next_bool() {
+                Cell::Dead
+            } else {
+                Cell::Alive
+            };
+        }
+        let colors = self.mode.colors() as u64;
+        for color in self.colors.iter_mut() {
+            *color = (self.rng.next_u64() % colors) as u8;
+        }
+        self.reset_stats();
+    }
+
+    /// Reseed the generator used by `randomize`, so the following boards are reproducible.
+    pub fn seed_stream(&mut self, seed: u64) {
+        self.rng = Rng::new(seed);
+    }
+
+    /// Replace the board with `num_clusters` blobs of about `cluster_size` live cells,
+    /// each grown outward from a random seed cell.
+    pub fn randomize_clustered(&mut self, seed: u64, num_clusters: u32, cluster_size: u32) {
+        if self.frozen {
+            return;
+        }
+        self.record(|| {
+            format!(
+                "randomize_clustered {} {} {}",
+                seed, num_clusters, cluster_size
+            )
+        });
+        let mut rng = Rng::new(seed);
+        self.cells.fill(Cell::Dead);
+        let colors = self.mode.colors() as u32;
+        for _ in 0..num_clusters {
+            let start = (rng.next_below(self.height), rng.next_below(self.width));
+            let mut blob = vec![start];
+            let mut attempts = cluster_size.saturating_mul(16);
+            while (blob.len() as u32) < cluster_size && attempts > 0 {
+                attempts -= 1;
+                let (row, column) = blob[rng.next_below(blob.len() as u32) as usize];
+                let neighbors = self.neighbors(row, column);
+                if neighbors.is_empty() {
+                    break;
+                }
+                let next = neighbors[rng.next_below(neighbors.len() as u32) as usize];
+                if !blob.contains(&next) {
+                    blob.push(next);
+                }
+            }
+            for (row, column) in blob.into_iter().take(cluster_size as usize) {
+                let idx = self.get_index(row, column);
+                self.cells[idx] = Cell::Alive;
+                self.colors[idx] = rng.next_below(colors) as u8;
+            }
+        }
+        self.reset_stats();
+    }
+
+    /// Fill the board with alternating cells, alive where `row + column` is even.
+    pub fn init_checkerboard(&mut self) {
+        self.fill_with(|row, column| (row + column).is_multiple_of(2));
+    }
+
+    /// Fill the board with vertical stripes `period` cells wide,
+    /// starting with a live stripe at column 0.
+    pub fn init_stripes(&mut self, period: u32) {
+        let period = period.max(1);
+        self.fill_with(|_, column| (column / period).is_multiple_of(2));
+    }
+
+    fn fill_with(&mut self, alive: impl Fn(u32, u32) -> bool) {
+        if self.frozen {
+            return;
+        }
+        let before = self.edit_start();
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let idx = self.get_index(row, column);
+                self.cells[idx] = if alive(row, column) {
+                    Cell::Alive
+                } else {
+                    Cell::Dead
+                };
+            }
+        }
+        self.record_edit(before);
+        self.reset_stats();
+    }
+
+    pub fn clear(&mut self) {
+        if self.frozen {
+            return;
+        }
+        self.record(|| "clear".to_string());
+        self.cells.fill(Cell::Dead);
+        self.reset_stats();
+    }
+
+    pub fn population(&self) -> u32 {
+        self.cells.iter().map(|c| *c as u32).sum()
+    }
+
+    /// Number of distinct 8-connected groups of live cells.
+    pub fn count_clusters(&self) -> u32 {
+        self.cluster_sizes().len() as u32
+    }
+
+    /// Cell count of the biggest 8-connected group of live cells.
+    pub fn largest_cluster_size(&self) -> u32 {
+        self.cluster_sizes().into_iter().max().unwrap_or(0)
+    }
+
+    /// Sorted distinct periods, from 2 up to `max_period`, of the oscillators on the board.
+    /// Oscillators are the 8-connected regions of cells alive at some point
+    /// over the next `2 * max_period` generations; a region's period is the smallest
+    /// with which all its cells repeat. The board itself is left untouched.
+    pub fn period_spectrum(&self, max_period: u32) -> Vec<u32> {
+        let mut scratch = self.scratch();
+        let mut history = vec![self.cells.clone()];
+        for _ in 0..2 * max_period {
+            scratch.tick_no_diff();
+            history.push(scratch.cells.clone());
+        }
+        let ever_alive: Vec<bool> = (0..self.cells.len())
+            .map(|i| history.iter().any(|cells| cells[i] == Cell::Alive))
+            .collect();
+        let mut periods: Vec<u32> = self
+            .components(&ever_alive)
+            .iter()
+            .filter_map(|region| {
+                (1..=max_period as usize).find(|p| {
+                    (0..history.len() - p)
+                        .all(|t| region.iter().all(|i| history[t][*i] == history[t + p][*i]))
+                })
+            })
+            .map(|p| p as u32)
+            .filter(|p| *p > 1)
+            .collect();
+        periods.sort_unstable();
+        periods.dedup();
+        periods
+    }
+
+    /// Number of live cells in a single row, 0 for rows outside the board.
+    pub fn row_population(&self, row: u32) -> u32 {
+        if row >= self.height {
+            return 0;
+        }
+        let start = self.get_index(row, 0);
+        self.cells[start..start + self.width as usize]
+            .iter()
+            .map(|c| *c as u32)
+            .sum()
+    }
+
+    /// Empty board of the given size with an RLE pattern centered at the given cell.
+    pub fn from_rle(
+        rle: &str,
+        width: u32,
+        height: u32,
+        row: u32,
+        column: u32,
+    ) -> Result<Universe, LifeError> {
+        if width == 0 || height == 0 || cell_count(width, height).is_none() {
+            return Err(LifeError::InvalidArgument(format!(
+                "Invalid board size: {}x{}",
+                width, height
+            )));
+        }
+        let pattern = Pattern::from_rle(rle)?;
+        let mut universe = Universe::empty(width, height);
+        universe.insert_pattern(&pattern, row, column, Blend::Overwrite)?;
+        universe.reset_stats();
+        Ok(universe)
+    }
+
+    /// Shannon entropy in bits of the live cell counts of `block`x`block` tiles.
+    /// Partial tiles at the right and bottom edges are ignored.
+    pub fn block_entropy(&self, block: u32) -> f64 {
+        if block == 0 || block > self.width || block > self.height {
+            return 0.0;
+        }
+        let mut histogram = vec![0u32; (block * block + 1) as usize];
+        for tile_row in 0..self.height / block {
+            for tile_column in 0..self.width / block {
+                let mut count = 0;
+                for row in tile_row * block..(tile_row + 1) * block {
+                    let start = self.get_index(row, tile_column * block);
+                    count += self.cells[start..start + block as usize]
+                        .iter()
+                        .map(|c| *c as usize)
+                        .sum::<usize>();
+                }
+                histogram[count] += 1;
+            }
+        }
+        let tiles = ((self.height / block) * (self.width / block)) as f64;
+        histogram
+            .into_iter()
+            .filter(|n| *n > 0)
+            .map(|n| {
+                let p = n as f64 / tiles;
+                -p * p.log2()
+            })
+            .sum()
+    }
+
+    /// Coarser view of the board for minimaps: the live cell count of each
+    /// `factor`x`factor` block, row by row, saturating at 255.
+    /// Partial blocks at the right and bottom edges are kept, so the grid is
+    /// `ceil(width / factor)` blocks wide and `ceil(height / factor)` tall.
+    pub fn downsample(&self, factor: u32) -> Vec<u8> {
+        if factor == 0 {
+            return Vec::new();
+        }
+        let columns = self.width.div_ceil(factor) as usize;
+        let rows = self.height.div_ceil(factor) as usize;
+        let mut counts = vec![0u8; columns * rows];
+        for (i, cell) in self.cells.iter().enumerate() {
+            if *cell == Cell::Alive {
+                let (row, column) = (i as u32 / self.width, i as u32 % self.width);
+                let block = (row / factor) as usize * columns + (column / factor) as usize;
+                counts[block] = counts[block].saturating_add(1);
+            }
+        }
+        counts
+    }
+
+    /// Live region of the board in run length encoded format, with a header
+    /// giving its size and the rule. Lines are kept under 70 characters.
+    pub fn to_rle(&self) -> String {
+        let (row, column, width, height) = self.live_bounds().unwrap_or((0, 0, 0, 0));
+        let mut tokens = vec![];
+        let mut pending_rows = 0;
+        for r in row..row + height {
+            let start = self.get_index(r, column);
+            let line = &self.cells[start..start + width as usize];
+            let end = line
+                .iter()
+                .rposition(|c| *c == Cell::Alive)
+                .map_or(0, |i| i + 1);
+            if end == 0 {
+                pending_rows += 1;
+                continue;
+            }
+            if r != row {
+                tokens.push(run_token(pending_rows + 1, '$'));
+            }
+            pending_rows = 0;
+            let mut i = 0;
+            while i < end {
+                let run = line[i..end].iter().take_while(|c| **c == line[i]).count();
+                let tag = if line[i] == Cell::Alive { 'o' } else { 'b' };
+                tokens.push(run_token(run, tag));
+                i += run;
+            }
+        }
+        tokens.push("!".to_string());
+
+        let mut rle = format!("x = {}, y = {}, rule = {}\n", width, height, self.rule);
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length + token.len() > 70 {
+                rle.push('\n');
+                line_length = 0;
+            }
+            line_length += token.len();
+            rle.push_str(&token);
+        }
+        rle.push('\n');
+        rle
+    }
+
+    /// Bring to life every cell that is alive in another, no larger, board
+    /// placed with its top left corner at the given offset, wrapping around the edges.
+    pub fn overlay(
+        &mut self,
+        other: &Universe,
+        row_offset: u32,
+        col_offset: u32,
+    ) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        if other.width > self.width || other.height > self.height {
+            return Err(LifeError::DimensionMismatch {
+                expected: (self.width, self.height),
+                found: (other.width, other.height),
+            });
+        }
+        let before = self.edit_start();
+        for (i, cell) in other.cells.iter().enumerate() {
+            if *cell == Cell::Alive {
+                let (row, column) = (i as u32 / other.width, i as u32 % other.width);
+                let idx = self.get_index(
+                    (row + row_offset) % self.height,
+                    (column + col_offset) % self.width,
+                );
+                self.cells[idx] = Cell::Alive;
+            }
+        }
+        self.record_edit(before);
+        Ok(())
+    }
+
+    /// Make the board read-only: ticks and edits of the cells are ignored,
+    /// or fail when they can report an error, until `unfreeze` is called.
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    pub fn unfreeze(&mut self) {
+        self.frozen = false;
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Start logging toggles, insertions, ticks, randomizations, clears
+    /// and changes of the settings that affect ticks, so `replay` can reproduce the session.
+    /// The log begins with the board's size, rules, boundary, mode, noise,
+    /// scan order and live cells with their colors.
+    /// Other edits of the cells, such as `set_cells` or `overlay`, are logged as toggles
+    /// and colors of the cells they change. Resizing stops the recording.
+    pub fn start_recording(&mut self) {
+        let mut log = vec![
+            format!("size {} {}", self.width, self.height),
+            format!("rule {}", self.rule),
+            format!("boundary {:?}", self.boundary),
+            format!("mode {:?}", self.mode),
+            format!("color_tiebreak {:?}", self.color_tiebreak),
+            format!("scan_order {:?}", self.scan_order),
+        ];
+        if let Some((birth_p, death_p, rng)) = &self.noise {
+            log.push(format!("noise {} {} {}", birth_p, death_p, rng.state()));
+        }
+        for row in 0..self.height {
+            let start = self.get_index(row, 0);
+            let line = &self.rule_index[start..start + self.width as usize];
+            let mut column = 0;
+            while column < line.len() {
+                let run = line[column..]
+                    .iter()
+                    .take_while(|i| **i == line[column])
+                    .count();
+                if line[column] > 0 {
+                    let rule = self.region_rules[line[column] as usize - 1];
+                    log.push(format!("rule_region {} {} {} 1 {}", row, column, run, rule));
+                }
+                column += run;
+            }
+        }
+        for (idx, cell) in self.cells.iter().enumerate() {
+            if *cell == Cell::Alive {
+                let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+                log.push(format!("toggle {} {}", row, column));
+                if self.colors[idx] != 0 {
+                    log.push(format!("color {} {} {}", row, column, self.colors[idx]));
+                }
+            }
+        }
+        self.recording = Some(log);
+    }
+
+    /// Stop recording and return the log, empty if nothing was being recorded.
+    pub fn stop_recording(&mut self) -> String {
+        self.recording
+            .take()
+            .map(|log| log.join("\n") + "\n")
+            .unwrap_or_default()
+    }
+
+    /// Commands recorded so far, one per line.
+    pub fn recorded_commands(&self) -> String {
+        self.recording
+            .as_ref()
+            .map(|log| log.join("\n") + "\n")
+            .unwrap_or_default()
+    }
+
+    /// Build a fresh universe by applying the commands of a log
+    /// produced by `start_recording`.
+    pub fn replay(log: &str) -> Result<Universe, LifeError> {
+        let mut lines = log
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .filter(|(_, l)| !l.trim().is_empty());
+        let (line, header) = lines.next().unwrap_or((1, ""));
+        let size: Vec<u32> = match header.split_whitespace().collect::<Vec<_>>()[..] {
+            ["size", width, height] => [width, height]
+                .iter()
+                .filter_map(|n| n.parse().ok())
+                .filter(|n| *n > 0)
+                .collect(),
+            _ => vec![],
+        };
+        let [width, height] = size[..] else {
+            return Err(LifeError::ParseError {
+                line,
+                msg: format!("Expected `size width height`, got [{}]", header),
+            });
+        };
+        if cell_count(width, height).is_none() {
+            return Err(LifeError::ParseError {
+                line,
+                msg: format!("A {}x{} board has too many cells", width, height),
+            });
+        }
+        let mut universe = Universe::empty(width, height);
+        for (line, command) in lines {
+            universe
+                .apply_command(command)
+                .map_err(|msg| LifeError::ParseError { line, msg })?;
+        }
+        universe.reset_stats();
+        Ok(universe)
+    }
+
+    /// Append a command to the log if recording.
+    fn record(&mut self, command: impl FnOnce() -> String) {
+        if let Some(log) = &mut self.recording {
+            log.push(command());
+        }
+    }
+
+    /// Cells and colors before an edit that has no command of its own, if recording.
+    fn edit_start(&self) -> Option<(Vec<Cell>, Vec<u8>)> {
+        self.recording
+            .as_ref()
+            .map(|_| (self.cells.clone(), self.colors.clone()))
+    }
+
+    /// Log what changed since `edit_start` as toggles and colors of the affected cells.
+    fn record_edit(&mut self, before: Option<(Vec<Cell>, Vec<u8>)>) {
+        let (Some((cells, colors)), Some(log)) = (before, &mut self.recording) else {
+            return;
+        };
+        for idx in 0..self.cells.len() {
+            let (row, column) = (idx as u32 / self.width, idx as u32 % self.width);
+            if self.cells[idx] != cells[idx] {
+                log.push(format!("toggle {} {}", row, column));
+            }
+            if self.colors[idx] != colors[idx] {
+                log.push(format!("color {} {} {}", row, column, self.colors[idx]));
+            }
+        }
+    }
+
+    /// Apply one line of a log produced by `start_recording`.
+    fn apply_command(&mut self, line: &str) -> Result<(), String> {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let number = |i: usize| -> Result<u64, String> {
+            parts
+                .get(i)
+                .and_then(|p| p.parse().ok())
+                .ok_or_else(|| format!("Invalid command [{}]", line))
+        };
+        match parts[..] {
+            ["rule", rule] => self.set_rule(rule).map_err(|e| e.to_string())?,
+            ["boundary", "Toroidal"] => self.boundary = Boundary::Toroidal,
+            ["boundary", "Dead"] => self.boundary = Boundary::Dead,
+            ["boundary", "Mobius"] => self.boundary = Boundary::Mobius,
+            ["mode", "Classic"] => self.set_mode(Mode::Classic),
+            ["mode", "Immigration"] => self.set_mode(Mode::Immigration),
+            ["mode", "QuadLife"] => self.set_mode(Mode::QuadLife),
+            ["scan_order", "Shuffled"] => self.scan_order = ScanOrder::Shuffled,
+            ["scan_order", "RowMajor"] => self.scan_order = ScanOrder::RowMajor,
+            ["scan_order", "ColumnMajor"] => self.scan_order = ScanOrder::ColumnMajor,
+            ["scan_order", "BoustrophedonRow"] => self.scan_order = ScanOrder::BoustrophedonRow,
+            ["toggle", _, _] => {
+                let (row, column) = (number(1)? as u32, number(2)? as u32);
+                if row >= self.height || column >= self.width {
+                    return Err(format!("Cell out of bounds in [{}]", line));
+                }
+                self.toggle_cell(row, column);
+            }
+            ["stamp", _, _, _, _, blend, ..] => {
+                let blend = match blend {
+                    "Overwrite" => Blend::Overwrite,
+                    "Or" => Blend::Or,
+                    _ => return Err(format!("Invalid blend in [{}]", line)),
+                };
+                let alive_cells = parts[6..]
+                    .iter()
+                    .map(|cell| {
+                        let (x, y) = cell.split_once(',')?;
+                        Some((x.parse().ok()?, y.parse().ok()?))
+                    })
+                    .collect::<Option<Vec<(u32, u32)>>>()
+                    .ok_or_else(|| format!("Invalid cells in [{}]", line))?;
+                let pattern = Pattern {
+                    alive_cells,
+                    width: number(3)? as u32,
+                    height: number(4)? as u32,
+                };
+                self.check_fits(&pattern).map_err(|e| e.to_string())?;
+                let (row, column) = (number(1)? as u32, number(2)? as u32);
+                self.stamp_pattern(&pattern, row, column, blend);
+            }
+            ["tick"] => self.tick(),
+            ["tick_region", _, _, _, _] => self.tick_region(
+                number(1)? as u32,
+                number(2)? as u32,
+                number(3)? as u32,
+                number(4)? as u32,
+            ),
+            ["tick_async", _] => self.tick_async(number(1)?),
+            ["randomize", _] => {
+                self.rng = Rng::new(number(1)?);
+                self.randomize();
+            }
+            ["randomize_clustered", _, _, _] => {
+                self.randomize_clustered(number(1)?, number(2)? as u32, number(3)? as u32)
+            }
+            ["clear"] => self.clear(),
+            ["color_tiebreak", "Lowest"] => self.color_tiebreak = ColorTiebreak::Lowest,
+            ["color_tiebreak", "Highest"] => self.color_tiebreak = ColorTiebreak::Highest,
+            ["noise", birth_p, death_p, _] => {
+                let (birth_p, death_p) = match (birth_p.parse(), death_p.parse()) {
+                    (Ok(birth_p), Ok(death_p)) => (birth_p, death_p),
+                    _ => return Err(format!("Invalid probabilities in [{}]", line)),
+                };
+                self.set_noise(birth_p, death_p, number(3)?)
+                    .map_err(|e| e.to_string())?;
+            }
+            ["rule_region", _, _, _, _, rule] => self
+                .set_rule_region(
+                    number(1)? as u32,
+                    number(2)? as u32,
+                    number(3)? as u32,
+                    number(4)? as u32,
+                    rule,
+                )
+                .map_err(|e| e.to_string())?,
+            ["color", _, _, _] => self
+                .set_cell_color(number(1)? as u32, number(2)? as u32, number(3)? as u8)
+                .map_err(|e| e.to_string())?,
+            _ => return Err(format!("Unknown command [{}]", line)),
+        }
+        Ok(())
+    }
+
+    fn check_unfrozen(&self) -> Result<(), LifeError> {
+        if self.frozen {
+            return Err(LifeError::Frozen);
+        }
+        Ok(())
+    }
+
+    /// Check internal consistency: buffer sizes match the dimensions,
+    /// and the diff lists unique in-range indices terminated by -1.
+    pub fn debug_invariants(&self) -> Result<(), LifeError> {
+        let size = (self.width * self.height) as usize;
+        let lengths = [
+            ("cells", self.cells.len()),
+            ("buffer", self.buffer.len()),
+            ("diff", self.diff.len()),
+            ("in_diff", self.in_diff.len()),
+            ("fade", self.fade.len()),
+            ("tags", self.tags.len()),
+            ("ages", self.ages.len()),
+            ("rule_index", self.rule_index.len()),
+            ("colors", self.colors.len()),
+            ("color_buffer", self.color_buffer.len()),
+        ];
+        for (name, length) in lengths.iter() {
+            if *length != size {
+                return Err(LifeError::BrokenInvariant(format!(
+                    "{} has {} entries, expected {} for a {}x{} board",
+                    name, length, size, self.width, self.height
+                )));
+            }
+        }
+        if self.diff_count > size {
+            return Err(LifeError::BrokenInvariant(format!(
+                "diff_count {} exceeds the board size {}",
+                self.diff_count, size
+            )));
+        }
+        let mut seen = vec![false; size];
+        for i in self.changed_indices() {
+            if i >= size {
+                return Err(LifeError::BrokenInvariant(format!(
+                    "diff index {} is out of range",
+                    i
+                )));
+            }
+            if seen[i] {
+                return Err(LifeError::BrokenInvariant(format!(
+                    "diff index {} is listed twice",
+                    i
+                )));
+            }
+            seen[i] = true;
+        }
+        if seen != self.in_diff {
+            return Err(LifeError::BrokenInvariant(
+                "diff markers don't match the listed indices".to_string(),
+            ));
+        }
+        if let Some(i) = self.diff[self.diff_count..].iter().find(|i| **i != -1) {
+            return Err(LifeError::BrokenInvariant(format!(
+                "diff has index {} past diff_count",
+                i
+            )));
+        }
+        if let Some(i) = self.wrapped_changes.iter().find(|i| !seen[**i as usize]) {
+            return Err(LifeError::BrokenInvariant(format!(
+                "wrapped change {} is missing from the diff",
+                i
+            )));
+        }
+        Ok(())
+    }
+
+    /// Flat indices of cells that differ between this board and another of the same size.
+    pub fn diff_against(&self, other: &Universe) -> Result<Vec<u32>, LifeError> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(LifeError::DimensionMismatch {
+                expected: (self.width, self.height),
+                found: (other.width, other.height),
+            });
+        }
+        Ok(self
+            .cells
+            .iter()
+            .zip(&other.cells)
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(i, _)| i as u32)
+            .collect())
+    }
+
+    /// Number of live cells with at least one dead neighbor,
+    /// counting positions beyond a dead edge as dead.
+    pub fn live_perimeter(&self) -> u32 {
+        let mut count = 0;
+        for row in 0..self.height {
+            for column in 0..self.width {
+                if self.cells[self.get_index(row, column)] == Cell::Dead {
+                    continue;
+                }
+                let neighbors = self.neighbors(row, column);
+                if neighbors.len() < 8
+                    || neighbors
+                        .iter()
+                        .any(|(r, c)| self.cells[self.get_index(*r, *c)] == Cell::Dead)
+                {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Number of live cells in the box of the given radius around a cell,
+    /// wrapping around the edges and including the cell itself.
+    pub fn local_population(&self, row: u32, column: u32, radius: u32) -> u32 {
+        let side = |size: u32| radius.saturating_mul(2).saturating_add(1).min(size);
+        let (rows, columns) = (side(self.height), side(self.width));
+        let top = row % self.height + self.height - (rows - 1) / 2;
+        let left = column % self.width + self.width - (columns - 1) / 2;
+        let mut count = 0;
+        for r in 0..rows {
+            for c in 0..columns {
+                let i = self.get_index((top + r) % self.height, (left + c) % self.width);
+                count += self.cells[i] as u32;
+            }
+        }
+        count
+    }
+
+    /// Highest population observed since the last reset.
+    pub fn max_population(&self) -> u32 {
+        self.max_population
+    }
+
+    /// Lowest population observed since the last reset.
+    pub fn min_population(&self) -> u32 {
+        self.min_population
+    }
+
+    fn reset_stats(&mut self) {
+        self.generation = 0;
+        self.unchanged_ticks = 0;
+        self.snapshots.clear();
+        self.take_snapshot();
+        self.max_population = self.population();
+        self.min_population = self.max_population;
+    }
+
+    pub fn cells(&self) -> *const Cell {
+        self.cells.as_ptr()
+    }
+
+    /// Toggle cells given as a flat array of `row, column` pairs.
+    /// The diff is replaced with the cells whose state ends up changed, so it can be redrawn
+    /// (or they are appended to it in accumulate mode).
+    pub fn toggle_cells_flat(&mut self, coords: &[u32]) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        if !coords.len().is_multiple_of(2) {
+            return Err(LifeError::InvalidArgument(format!(
+                "Expected row, column pairs, got {} numbers",
+                coords.len()
+            )));
+        }
+        if let Some(pair) = coords
+            .chunks(2)
+            .find(|p| p[0] >= self.height || p[1] >= self.width)
+        {
+            return Err(LifeError::OutOfBounds {
+                row: pair[0],
+                column: pair[1],
+            });
+        }
+        let before = self.edit_start();
+        let mut original = BTreeMap::new();
+        for pair in coords.chunks(2) {
+            let idx = self.get_index(pair[0], pair[1]);
+            original.entry(idx).or_insert(self.cells[idx]);
+            self.cells[idx].toggle();
+        }
+        let changed: Vec<usize> = original
+            .into_iter()
+            .filter(|(idx, cell)| self.cells[*idx] != *cell)
+            .map(|(idx, _)| idx)
+            .collect();
+        if !self.diff_accumulate {
+            self.clear_diff();
+        }
+        for idx in changed {
+            self.record_change(idx);
+        }
+        self.record_edit(before);
+        Ok(())
+    }
+
+    /// Mirror the board across its main diagonal. Only square boards can be transposed.
+    pub fn transpose(&mut self) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        if self.width != self.height {
+            return Err(LifeError::DimensionMismatch {
+                expected: (self.width, self.width),
+                found: (self.width, self.height),
+            });
+        }
+        let before = self.edit_start();
+        for row in 0..self.height {
+            for col in row + 1..self.width {
+                let a = self.get_index(row, col);
+                let b = self.get_index(col, row);
+                self.cells.swap(a, b);
+            }
+        }
+        self.record_edit(before);
+        Ok(())
+    }
+
+    /// Fading intensity of dead cells: 255 right after dying,
+    /// lowered by `decay_fade` on every animation frame.
+    pub fn fade_buffer(&self) -> *const u8 {
+        self.fade.as_ptr()
+    }
+
+    pub fn decay_fade(&mut self, amount: u8) {
+        for intensity in self.fade.iter_mut() {
+            *intensity = intensity.saturating_sub(amount);
+        }
+    }
+
+    /// Generations the cell has survived since it was born, 0 for cells outside the board.
+    pub fn cell_age(&self, row: u32, column: u32) -> u16 {
+        self.checked_index(row, column)
+            .map_or(0, |idx| self.ages[idx])
+    }
+
+    /// Attach a tag to a cell. It's kept while the cell stays alive and cleared when it dies.
+    /// Cells outside the board are ignored.
+    pub fn tag_cell(&mut self, row: u32, column: u32, tag: u8) {
+        if let Some(idx) = self.checked_index(row, column) {
+            self.tags[idx] = tag;
+        }
+    }
 
-..OOO...OOO
-O....O.O....O
-O....O.O....O
-O....O.O....O
+    pub fn tag_ptr(&self) -> *const u8 {
+        self.tags.as_ptr()
+    }
+
+    /// Previous generation's cells, for cross-fading between frames.
+    /// Only valid immediately after a tick: the buffer is overwritten by the next one.
+    pub fn prev_cells(&self) -> *const Cell {
+        self.buffer.as_ptr()
+    }
+
+    pub fn toggle_cell(&mut self, row: u32, column: u32) {
+        if self.frozen {
+            return;
+        }
+        self.record(|| format!("toggle {} {}", row, column));
+        let idx = self.get_index(row, column);
+        self.cells[idx].toggle();
+        self.ages[idx] = 0;
+        self.unchanged_ticks = 0;
+    }
+
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.record(|| format!("boundary {:?}", boundary));
+        self.boundary = boundary;
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.record(|| format!("mode {:?}", mode));
+        self.mode = mode;
+        self.colors.fill(0);
+    }
+
+    pub fn color_tiebreak(&self) -> ColorTiebreak {
+        self.color_tiebreak
+    }
+
+    /// Choose how ties between parent colors are broken. The default is `Highest`.
+    pub fn set_color_tiebreak(&mut self, tiebreak: ColorTiebreak) {
+        self.record(|| format!("color_tiebreak {:?}", tiebreak));
+        self.color_tiebreak = tiebreak;
+    }
+
+    /// Colors of the cells, meaningful only in colored modes.
+    pub fn colors(&self) -> *const u8 {
+        self.colors.as_ptr()
+    }
+
+    /// Color of a cell, 0 for cells outside the board.
+    pub fn cell_color(&self, row: u32, column: u32) -> u8 {
+        self.checked_index(row, column)
+            .map_or(0, |idx| self.colors[idx])
+    }
+
+    pub fn set_cell_color(&mut self, row: u32, column: u32, color: u8) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let idx = self
+            .checked_index(row, column)
+            .ok_or(LifeError::OutOfBounds { row, column })?;
+        if color >= self.mode.colors() {
+            return Err(LifeError::InvalidArgument(format!(
+                "Color {} is not available in {:?} mode",
+                color, self.mode
+            )));
+        }
+        self.record(|| format!("color {} {} {}", row, column, color));
+        self.colors[idx] = color;
+        Ok(())
+    }
+
+    /// Live cells with more neighbors than this die of overcrowding.
+    /// The limit is applied to the survival counts given to `set_rule`, so calls don't
+    /// accumulate: counts above it are dropped, and a limit past the highest count
+    /// extends survival up to it. Lower counts are kept as they are.
+    pub fn set_overpopulation(&mut self, max_neighbors: u8) {
+        let max = (max_neighbors as usize).min(8);
+        let mut survival = self.rule_survival;
+        if let Some(highest) = survival.iter().rposition(|s| *s) {
+            for survives in survival.iter_mut().take(max + 1).skip(highest) {
+                *survives = true;
+            }
+        }
+        for survives in survival.iter_mut().skip(max + 1) {
+            *survives = false;
+        }
+        self.rule.survival = survival;
+        let rule = self.rule;
+        self.record(|| format!("rule {}", rule));
+    }
+
+    /// Current rule in `B3/S23` notation.
+    pub fn rule(&self) -> String {
+        self.rule.to_string()
+    }
+
+    /// Neighbor counts at which dead cells are born under the main rule, in ascending order.
+    pub fn birth_neighbors(&self) -> Vec<u8> {
+        (0..9).filter(|n| self.rule.birth[*n as usize]).collect()
+    }
+
+    /// Neighbor counts at which live cells survive under the main rule, in ascending order.
+    pub fn survival_neighbors(&self) -> Vec<u8> {
+        (0..9).filter(|n| self.rule.survival[*n as usize]).collect()
+    }
+
+    /// Switch to a Life-like rule given in `B3/S23` notation, e.g. `B36/S23` for HighLife.
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), LifeError> {
+        self.rule = rule.parse()?;
+        self.rule_survival = self.rule.survival;
+        let rule = self.rule;
+        self.record(|| format!("rule {}", rule));
+        Ok(())
+    }
+
+    /// Run cells inside the rectangle under a different rule than the rest of the board.
+    /// The neighbors they count may lie outside the region.
+    pub fn set_rule_region(
+        &mut self,
+        row: u32,
+        column: u32,
+        width: u32,
+        height: u32,
+        rule: &str,
+    ) -> Result<(), LifeError> {
+        let rule: Rule = rule.parse()?;
+        let index = match self.region_rules.iter().position(|r| *r == rule) {
+            Some(i) => i + 1,
+            None if self.region_rules.len() < u8::MAX as usize => {
+                self.region_rules.push(rule);
+                self.region_rules.len()
+            }
+            None => {
+                return Err(LifeError::InvalidArgument(format!(
+                    "At most {} different region rules are supported",
+                    u8::MAX
+                )))
+            }
+        };
+        self.record(|| {
+            format!(
+                "rule_region {} {} {} {} {}",
+                row, column, width, height, rule
+            )
+        });
+        for r in row..row.saturating_add(height).min(self.height) {
+            for c in column..column.saturating_add(width).min(self.width) {
+                let idx = self.get_index(r, c);
+                self.rule_index[idx] = index as u8;
+            }
+        }
+        Ok(())
+    }
+
+    /// Human readable summary of the universe's settings.
+    pub fn config_summary(&self) -> String {
+        format!(
+            "width: {}\nheight: {}\nrule: {}\nboundary: {:?}\nneighborhood: Moore\nmode: {:?}\ngeneration: {}",
+            self.width, self.height, self.rule, self.boundary, self.mode, self.generation
+        )
+    }
+
+    /// Patterns larger than the board would overlap themselves when wrapped.
+    fn check_fits(&self, pattern: &Pattern) -> Result<(), LifeError> {
+        if pattern.width > self.width || pattern.height > self.height {
+            return Err(LifeError::DimensionMismatch {
+                expected: (self.width, self.height),
+                found: (pattern.width, pattern.height),
+            });
+        }
+        Ok(())
+    }
+
+    /// Insert pattern centered at the given cell, wrapping around the edges.
+    fn insert_pattern(
+        &mut self,
+        pattern: &Pattern,
+        row: u32,
+        column: u32,
+        blend: Blend,
+    ) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        self.check_fits(pattern)?;
+        let row = (row % self.height + self.height - pattern.height / 2) % self.height;
+        let column = (column % self.width + self.width - pattern.width / 2) % self.width;
+        self.stamp_pattern(pattern, row, column, blend);
+        Ok(())
+    }
+
+    /// Insert pattern with its top left corner at the given cell.
+    fn stamp_pattern(&mut self, pattern: &Pattern, row: u32, column: u32, blend: Blend) {
+        let (row, column) = (row % self.height, column % self.width);
+        self.record(|| {
+            let cells: Vec<String> = pattern
+                .alive_cells
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect();
+            format!(
+                "stamp {} {} {} {} {:?} {}",
+                row,
+                column,
+                pattern.width,
+                pattern.height,
+                blend,
+                cells.join(" ")
+            )
+        });
+        if blend == Blend::Overwrite {
+            for x in 0..pattern.height {
+                for y in 0..pattern.width {
+                    let x = (row + x) % self.height;
+                    let y = (column + y) % self.width;
+                    let i = self.get_index(x, y);
+                    self.cells[i] = Cell::Dead;
+                }
+            }
+        }
+        for (x, y) in &pattern.alive_cells {
+            let x = (row + x) % self.height;
+            let y = (column + y) % self.width;
+            let i = self.get_index(x, y);
+            self.cells[i] = Cell::Alive;
+        }
+    }
+
+    /// Insert pattern centered at the given cell, combining it with the background
+    /// as specified by `blend`.
+    pub fn insert_pattern_blended(
+        &mut self,
+        schema: &str,
+        row: u32,
+        column: u32,
+        blend: Blend,
+    ) -> Result<(), LifeError> {
+        let pattern: Pattern = schema.parse()?;
+        self.insert_pattern(&pattern, row, column, blend)
+    }
+
+    /// Stamp a bitmap with its top left corner at the given cell, wrapping around the edges.
+    /// Bits are packed row by row, least significant bit first, as in `serialize`.
+    pub fn insert_bitmap(
+        &mut self,
+        bits: &[u8],
+        width: u32,
+        height: u32,
+        row: u32,
+        column: u32,
+    ) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let size = cell_count(width, height).ok_or_else(|| {
+            LifeError::InvalidArgument(format!("A {}x{} bitmap is too large", width, height))
+        })?;
+        if bits.len() < size.div_ceil(8) {
+            return Err(LifeError::InvalidArgument(format!(
+                "A {}x{} bitmap needs {} bytes, got {}",
+                width,
+                height,
+                size.div_ceil(8),
+                bits.len()
+            )));
+        }
+        let pattern = Pattern {
+            alive_cells: (0..size)
+                .filter(|i| bits[i / 8] >> (i % 8) & 1 == 1)
+                .map(|i| (i as u32 / width, i as u32 % width))
+                .collect(),
+            width,
+            height,
+        };
+        self.check_fits(&pattern)?;
+        self.stamp_pattern(&pattern, row, column, Blend::Overwrite);
+        Ok(())
+    }
+
+    /// Insert pattern centered at the given cell together with its mirror images
+    /// across the board center, as chosen by `axis`. The copies are added
+    /// to the background, so where they overlap they are merged.
+    pub fn insert_pattern_mirrored(
+        &mut self,
+        schema: &str,
+        row: u32,
+        column: u32,
+        axis: Symmetry,
+    ) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let pattern: Pattern = schema.parse()?;
+        self.check_fits(&pattern)?;
+        let top = (row % self.height + self.height - pattern.height / 2) % self.height;
+        let left = (column % self.width + self.width - pattern.width / 2) % self.width;
+        let bottom = (2 * self.height - top - pattern.height) % self.height;
+        let right = (2 * self.width - left - pattern.width) % self.width;
+        let mut copies = vec![(pattern.clone(), top, left)];
+        if axis != Symmetry::Vertical {
+            copies.push((pattern.mirrored(true, false), top, right));
+        }
+        if axis != Symmetry::Horizontal {
+            copies.push((pattern.mirrored(false, true), bottom, left));
+        }
+        if axis == Symmetry::Both {
+            copies.push((pattern.mirrored(true, true), bottom, right));
+        }
+        for (copy, row, column) in &copies {
+            self.stamp_pattern(copy, *row, *column, Blend::Or);
+        }
+        Ok(())
+    }
+
+    /// Insert pattern centered at the cell under a point given as fractions
+    /// of the board's width (`fx`) and height (`fy`), each in `[0, 1)`.
+    pub fn insert_at_fraction(&mut self, schema: &str, fx: f64, fy: f64) -> Result<(), LifeError> {
+        if !(0.0..1.0).contains(&fx) || !(0.0..1.0).contains(&fy) {
+            return Err(LifeError::InvalidArgument(format!(
+                "Fractions must be in [0, 1), got ({}, {})",
+                fx, fy
+            )));
+        }
+        let column = ((fx * self.width as f64) as u32).min(self.width - 1);
+        let row = ((fy * self.height as f64) as u32).min(self.height - 1);
+        let pattern: Pattern = schema.parse()?;
+        self.insert_pattern(&pattern, row, column, Blend::Overwrite)
+    }
+
+    /// Insert pattern centered at the given cell, refusing to do so
+    /// when any of its live cells would land on a live cell.
+    pub fn insert_pattern_checked(
+        &mut self,
+        schema: &str,
+        row: u32,
+        column: u32,
+    ) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let pattern: Pattern = schema.parse()?;
+        self.check_fits(&pattern)?;
+        let top = row % self.height + self.height - pattern.height / 2;
+        let left = column % self.width + self.width - pattern.width / 2;
+        let conflicts = pattern
+            .alive_cells
+            .iter()
+            .filter(|(x, y)| {
+                let i = self.get_index((top + x) % self.height, (left + y) % self.width);
+                self.cells[i] == Cell::Alive
+            })
+            .count();
+        if conflicts > 0 {
+            return Err(LifeError::InvalidArgument(format!(
+                "Pattern overlaps {} live cells at ({}, {})",
+                conflicts, row, column
+            )));
+        }
+        self.insert_pattern(&pattern, row, column, Blend::Or)
+    }
+
+    /// Insert pattern with its top left corner at the given cell.
+    /// Fails instead of wrapping when the pattern doesn't fit across a dead edge:
+    /// any edge on a dead-boundary universe, the top and bottom ones on a Möbius strip,
+    /// where crossing the left and right edges flips the pattern upside down.
+    pub fn try_insert_pattern(
+        &mut self,
+        schema: &str,
+        row: u32,
+        column: u32,
+    ) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let pattern: Pattern = schema.parse()?;
+        self.check_fits(&pattern)?;
+        let fits = |start: u32, size: u32, limit: u32| {
+            start.checked_add(size).is_some_and(|end| end <= limit)
+        };
+        let rows_fit = fits(row, pattern.height, self.height);
+        let fitting = match self.boundary {
+            Boundary::Toroidal => true,
+            Boundary::Dead => rows_fit && fits(column, pattern.width, self.width),
+            Boundary::Mobius => rows_fit,
+        };
+        if !fitting {
+            return Err(LifeError::OutOfBounds {
+                row: row.saturating_add(pattern.height - 1),
+                column: column.saturating_add(pattern.width - 1),
+            });
+        }
+        if self.boundary == Boundary::Mobius {
+            self.stamp_on_strip(&pattern, row, column % self.width);
+        } else {
+            self.stamp_pattern(&pattern, row, column, Blend::Overwrite);
+        }
+        Ok(())
+    }
+
+    /// Overwrite the pattern's bounding box on a Möbius strip, following the flip
+    /// across the left and right edges. The rows must fit.
+    fn stamp_on_strip(&mut self, pattern: &Pattern, row: u32, column: u32) {
+        let before = self.edit_start();
+        let (height, width) = (self.height, self.width);
+        let at = |x: u32, y: u32| {
+            let (r, c) = Boundary::Mobius
+                .resolve(
+                    row as i64 + x as i64,
+                    column as i64 + y as i64,
+                    height,
+                    width,
+                )
+                .unwrap();
+            r as usize * width as usize + c as usize
+        };
+        for x in 0..pattern.height {
+            for y in 0..pattern.width {
+                let i = at(x, y);
+                self.cells[i] = Cell::Dead;
+            }
+        }
+        for (x, y) in &pattern.alive_cells {
+            let i = at(*x, *y);
+            self.cells[i] = Cell::Alive;
+        }
+        self.record_edit(before);
+    }
+
+    /// Set every cell on the line from (r0, c0) to (r1, c1) to the given state,
+    /// wrapping coordinates beyond the edges.
+    pub fn set_line(&mut self, r0: u32, c0: u32, r1: u32, c1: u32, state: Cell) {
+        if self.frozen {
+            return;
+        }
+        let before = self.edit_start();
+        for (row, column) in line_points(r0, c0, r1, c1) {
+            let idx = self.get_index(row % self.height, column % self.width);
+            self.cells[idx] = state;
+        }
+        self.record_edit(before);
+    }
+
+    /// Insert pattern every `spacing` cells along the line from (r0, c0) to (r1, c1).
+    pub fn stamp_line(
+        &mut self,
+        schema: &str,
+        r0: u32,
+        c0: u32,
+        r1: u32,
+        c1: u32,
+        spacing: u32,
+    ) -> Result<(), LifeError> {
+        if spacing == 0 {
+            return Err(LifeError::InvalidArgument(
+                "Spacing must be positive".to_string(),
+            ));
+        }
+        let pattern: Pattern = schema.parse()?;
+        self.check_fits(&pattern)?;
+        for (row, column) in line_points(r0, c0, r1, c1)
+            .into_iter()
+            .step_by(spacing as usize)
+        {
+            self.insert_pattern(&pattern, row, column, Blend::Overwrite)?;
+        }
+        Ok(())
+    }
+
+    /// Insert known patterns described by lines of `name row column`.
+    /// Nothing is inserted if any line is invalid.
+    pub fn insert_scene(&mut self, placements: &str) -> Result<(), LifeError> {
+        let mut scene = vec![];
+        for (i, line) in placements.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let invalid = |msg: String| LifeError::ParseError { line: i + 1, msg };
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            let [name, row, column] = parts[..] else {
+                return Err(invalid(format!(
+                    "Expected `name row column`, got [{}]",
+                    line
+                )));
+            };
+            let schema = patterns::schema(name)
+                .ok_or_else(|| invalid(format!("Unknown pattern: {}", name)))?;
+            let row: u32 = row
+                .parse()
+                .map_err(|_| invalid(format!("Invalid row in [{}]", line)))?;
+            let column: u32 = column
+                .parse()
+                .map_err(|_| invalid(format!("Invalid column in [{}]", line)))?;
+            let pattern: Pattern = schema.parse()?;
+            self.check_fits(&pattern)?;
+            scene.push((pattern, row, column));
+        }
+        for (pattern, row, column) in &scene {
+            self.insert_pattern(pattern, *row, *column, Blend::Overwrite)?;
+        }
+        Ok(())
+    }
+
+    /// Insert patterns described by a JSON array of `{"pattern", "row", "col", "rotation"}`
+    /// objects, each centered at its cell. `pattern` is a known pattern name or a pattern
+    /// in plaintext format, and the optional `rotation` is clockwise in degrees.
+    /// Nothing is inserted if any object is invalid.
+    #[cfg(feature = "json-scene")]
+    pub fn load_scene_json(&mut self, json: &str) -> Result<(), LifeError> {
+        self.check_unfrozen()?;
+        let placements: Vec<ScenePlacement> =
+            serde_json::from_str(json).map_err(|e| LifeError::ParseError {
+                line: e.line(),
+                msg: e.to_string(),
+            })?;
+        let mut scene = vec![];
+        for placement in placements {
+            if !placement.rotation.is_multiple_of(90) {
+                return Err(LifeError::InvalidArgument(format!(
+                    "Rotation must be a multiple of 90 degrees, got {}",
+                    placement.rotation
+                )));
+            }
+            let schema = patterns::schema(&placement.pattern).unwrap_or(&placement.pattern);
+            let pattern: Pattern = schema.parse()?;
+            let pattern = pattern.rotated(placement.rotation / 90);
+            self.check_fits(&pattern)?;
+            scene.push((pattern, placement.row, placement.col));
+        }
+        for (pattern, row, column) in &scene {
+            self.insert_pattern(pattern, *row, *column, Blend::Overwrite)?;
+        }
+        Ok(())
+    }
+
+    pub fn insert_glider(&mut self, row: u32, column: u32) -> Result<(), LifeError> {
+        let glider = patterns::GLIDER.parse().unwrap();
+        self.insert_pattern(&glider, row, column, Blend::Overwrite)
+    }
+
+    pub fn insert_pulsar(&mut self, row: u32, column: u32) -> Result<(), LifeError> {
+        let pulsar = patterns::PULSAR.parse().unwrap();
+        self.insert_pattern(&pulsar, row, column, Blend::Overwrite)
+    }
+
+    pub fn insert_r_pentomino(&mut self, row: u32, column: u32) -> Result<(), LifeError> {
+        let r_pentomino = patterns::R_PENTOMINO.parse().unwrap();
+        self.insert_pattern(&r_pentomino, row, column, Blend::Overwrite)
+    }
+
+    pub fn insert_acorn(&mut self, row: u32, column: u32) -> Result<(), LifeError> {
+        let acorn = patterns::ACORN.parse().unwrap();
+        self.insert_pattern(&acorn, row, column, Blend::Overwrite)
+    }
 
-..OOO...OOO".parse().unwrap();
-        self.insert_pattern(&pulsar, row, column);
+    pub fn insert_diehard(&mut self, row: u32, column: u32) -> Result<(), LifeError> {
+        let diehard = patterns::DIEHARD.parse().unwrap();
+        self.insert_pattern(&diehard, row, column, Blend::Overwrite)
     }
 }
@@ -0,0 +1,63 @@
+pub const GLIDER: &str = "!Name: Glider
+!Author: Richard K. Guy
+!The smallest, most common, and first discovered spaceship.
+!www.conwaylife.com/wiki/index.php?title=Glider
+.O
+..O
+OOO";
+
+pub const PULSAR: &str = "!Name: Pulsar
+!Author: John Conway
+!Despite its size, this is the fourth most common oscillator (and by far the most common of period greater than 2).
+!www.conwaylife.com/wiki/index.php?title=Pulsar
+..OOO...OOO
+
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO
+
+..OOO...OOO
+O....O.O....O
+O....O.O....O
+O....O.O....O
+
+..OOO...OOO";
+
+pub const R_PENTOMINO: &str = "!Name: R-pentomino
+!A methuselah that stabilizes after 1103 generations.
+!www.conwaylife.com/wiki/index.php?title=R-pentomino
+.OO
+OO.
+.O.";
+
+pub const ACORN: &str = "!Name: Acorn
+!Author: Charles Corderman
+!A methuselah that takes 5206 generations to stabilize.
+!www.conwaylife.com/wiki/index.php?title=Acorn
+.O
+...O
+OO..OOO";
+
+pub const DIEHARD: &str = "!Name: Die hard
+!A methuselah that vanishes after 130 generations.
+!www.conwaylife.com/wiki/index.php?title=Die_hard
+......O
+OO
+.O...OOO";
+
+const REGISTRY: &[(&str, &str)] = &[
+    ("glider", GLIDER),
+    ("pulsar", PULSAR),
+    ("r-pentomino", R_PENTOMINO),
+    ("acorn", ACORN),
+    ("diehard", DIEHARD),
+];
+
+/// Find schema of a known pattern by its name.
+pub fn schema(name: &str) -> Option<&'static str> {
+    REGISTRY
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, schema)| *schema)
+}
@@ -0,0 +1,59 @@
+use std::{fmt, str::FromStr};
+
+use crate::LifeError;
+
+/// Life-like rule: neighbor counts at which dead cells are born and live cells survive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub birth: [bool; 9],
+    pub survival: [bool; 9],
+}
+
+impl Rule {
+    pub fn conway() -> Rule {
+        "B3/S23".parse().unwrap()
+    }
+}
+
+/// Parse a rulestring in `B3/S23` notation.
+impl FromStr for Rule {
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let invalid = || LifeError::ParseError {
+            line: 1,
+            msg: format!("Invalid rulestring, expected B.../S...: [{}]", rule),
+        };
+        let (birth, survival) = rule.trim().split_once('/').ok_or_else(invalid)?;
+        let parse_counts = |counts: &str, prefix: char| -> Result<[bool; 9], LifeError> {
+            let digits = counts
+                .strip_prefix(prefix)
+                .or_else(|| counts.strip_prefix(prefix.to_ascii_lowercase()))
+                .ok_or_else(invalid)?;
+            let mut table = [false; 9];
+            for digit in digits.chars() {
+                match digit.to_digit(10) {
+                    Some(n) if n < 9 => table[n as usize] = true,
+                    _ => return Err(invalid()),
+                }
+            }
+            Ok(table)
+        };
+        Ok(Rule {
+            birth: parse_counts(birth, 'B')?,
+            survival: parse_counts(survival, 'S')?,
+        })
+    }
+
+    type Err = LifeError;
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |table: &[bool; 9]| -> String {
+            (0..9)
+                .filter(|n| table[*n])
+                .map(|n| n.to_string())
+                .collect()
+        };
+        write!(f, "B{}/S{}", digits(&self.birth), digits(&self.survival))
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::{HashMap, HashSet};
+
+use wasm_bindgen::prelude::*;
+
+use crate::{rule::Rule, Boundary, LifeError};
+
+/// Universe storing only the coordinates of live cells,
+/// for very large and mostly empty boards where the dense `Universe` is wasteful.
+#[wasm_bindgen]
+pub struct SparseUniverse {
+    width: u32,
+    height: u32,
+    live: HashSet<(u32, u32)>,
+    rule: Rule,
+    boundary: Boundary,
+}
+
+impl SparseUniverse {
+    /// Set cells to be alive by passing the row and column of each cell.
+    pub fn set_cells(&mut self, cells: &[(u32, u32)]) {
+        self.live.extend(cells.iter().cloned());
+    }
+
+    /// Live cells ordered by row, then column.
+    pub fn live_cells(&self) -> Vec<(u32, u32)> {
+        let mut cells: Vec<(u32, u32)> = self.live.iter().cloned().collect();
+        cells.sort_unstable();
+        cells
+    }
+
+    fn neighbors(&self, row: u32, column: u32) -> impl Iterator<Item = (u32, u32)> + '_ {
+        const DELTAS: [i64; 3] = [-1, 0, 1];
+        DELTAS
+            .iter()
+            .flat_map(|dr| DELTAS.iter().map(move |dc| (*dr, *dc)))
+            .filter(|d| *d != (0, 0))
+            .filter_map(move |(dr, dc)| {
+                let (r, c) = (row as i64 + dr, column as i64 + dc);
+                self.boundary.resolve(r, c, self.height, self.width)
+            })
+    }
+}
+
+#[wasm_bindgen]
+impl SparseUniverse {
+    pub fn new(width: u32, height: u32) -> SparseUniverse {
+        SparseUniverse {
+            width,
+            height,
+            live: HashSet::new(),
+            rule: Rule::conway(),
+            boundary: Boundary::Toroidal,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn population(&self) -> u32 {
+        self.live.len() as u32
+    }
+
+    pub fn is_alive(&self, row: u32, column: u32) -> bool {
+        self.live.contains(&(row, column))
+    }
+
+    pub fn toggle_cell(&mut self, row: u32, column: u32) {
+        if !self.live.remove(&(row, column)) {
+            self.live.insert((row, column));
+        }
+    }
+
+    pub fn set_boundary(&mut self, boundary: Boundary) {
+        self.boundary = boundary;
+    }
+
+    /// Switch to a Life-like rule in `B3/S23` notation.
+    /// Rules with `B0` would fill the whole board and aren't supported.
+    pub fn set_rule(&mut self, rule: &str) -> Result<(), LifeError> {
+        let rule: Rule = rule.parse()?;
+        if rule.birth[0] {
+            return Err(LifeError::InvalidArgument(format!(
+                "Rule {} is not supported by sparse storage",
+                rule
+            )));
+        }
+        self.rule = rule;
+        Ok(())
+    }
+
+    /// Compute the next generation, visiting only live cells and their neighbors.
+    pub fn tick(&mut self) {
+        let mut counts: HashMap<(u32, u32), u8> = HashMap::with_capacity(self.live.len() * 8);
+        for (row, column) in &self.live {
+            for neighbor in self.neighbors(*row, *column) {
+                *counts.entry(neighbor).or_insert(0) += 1;
+            }
+        }
+        let survivors = self
+            .live
+            .iter()
+            .filter(|cell| self.rule.survival[*counts.get(cell).unwrap_or(&0) as usize]);
+        let births = counts
+            .iter()
+            .filter(|(cell, n)| !self.live.contains(cell) && self.rule.birth[**n as usize])
+            .map(|(cell, _)| cell);
+        self.live = survivors.chain(births).cloned().collect();
+    }
+}
@@ -1,5 +1,7 @@
 use web_sys::console;
 
+use crate::LifeError;
+
 pub fn set_panic_hook() {
     // When the `console_error_panic_hook` feature is enabled, we can call the
     // `set_panic_hook` function at least once during initialization, and then
@@ -19,10 +21,68 @@ macro_rules! log {
     }
 }
 
+/// CRC32 (IEEE) checksum of the given bytes.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encode bytes as URL-safe base64 without padding.
+pub fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64_ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+        }
+    }
+    encoded
+}
+
+/// Decode URL-safe base64, with or without padding.
+pub fn base64_decode(encoded: &str) -> Result<Vec<u8>, LifeError> {
+    let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+    let mut n = 0u32;
+    let mut bits = 0;
+    for c in encoded.trim_end_matches('=').chars() {
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|a| *a as char == c)
+            .ok_or_else(|| LifeError::ParseError {
+                line: 1,
+                msg: format!("Invalid base64 character: {:?}", c),
+            })?;
+        n = n << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[allow(dead_code)]
 pub struct Timer<'a> {
     name: &'a str,
 }
 
+#[allow(dead_code)]
 impl<'a> Timer<'a> {
     pub fn new(name: &'a str) -> Timer<'a> {
         console::time_with_label(name);
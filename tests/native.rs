@@ -0,0 +1,2037 @@
+//! Test suite for native targets.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use wasm_game_of_life::{
+    Blend, Boundary, Cell, ColorTiebreak, DiffResult, LifeError, Mode, Pattern, ScanOrder,
+    SparseUniverse, Symmetry, TickStrategy, Universe,
+};
+
+fn empty_universe() -> Universe {
+    let mut universe = Universe::new();
+    universe.clear();
+    universe
+}
+
+/// CRC32 (IEEE) checksum, for crafting serialized boards.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn population(universe: &Universe) -> usize {
+    universe
+        .get_cells()
+        .iter()
+        .filter(|c| **c == Cell::Alive)
+        .count()
+}
+
+#[test]
+fn try_insert_pattern_fails_past_dead_boundary() {
+    let block = "OOOOO
+OOOOO
+OOOOO
+OOOOO
+OOOOO";
+
+    let mut universe = empty_universe();
+    universe.set_boundary(Boundary::Dead);
+    assert!(universe.try_insert_pattern(block, 117, 117).is_err());
+    assert_eq!(population(&universe), 0);
+    assert!(universe.try_insert_pattern(block, 115, 115).is_ok());
+    assert_eq!(population(&universe), 25);
+
+    let mut universe = empty_universe();
+    assert!(universe.try_insert_pattern(block, 117, 117).is_ok());
+    assert_eq!(population(&universe), 25);
+}
+
+#[test]
+fn try_insert_pattern_respects_mobius_and_huge_coordinates() {
+    let mut universe = Universe::from_cells(6, 6, &[]);
+    universe.set_boundary(Boundary::Dead);
+    assert!(matches!(
+        universe.try_insert_pattern("OO", u32::MAX, 0),
+        Err(LifeError::OutOfBounds { .. })
+    ));
+    assert!(universe.try_insert_pattern("OO", 0, u32::MAX).is_err());
+    assert_eq!(universe.population(), 0);
+
+    // Crossing the right edge of a Möbius strip re-enters on the mirrored row.
+    universe.set_boundary(Boundary::Mobius);
+    universe.try_insert_pattern("OO\nO.", 1, 5).unwrap();
+    assert_eq!(universe.get_cell(1, 5), Cell::Alive);
+    assert_eq!(universe.get_cell(4, 0), Cell::Alive);
+    assert_eq!(universe.get_cell(2, 5), Cell::Alive);
+    assert_eq!(universe.population(), 3);
+    assert!(universe.try_insert_pattern("O\nO", 5, 0).is_err());
+    assert_eq!(universe.population(), 3);
+
+    universe.set_boundary(Boundary::Toroidal);
+    universe
+        .try_insert_pattern("O", u32::MAX, u32::MAX)
+        .unwrap();
+    assert_eq!(universe.get_cell(3, 3), Cell::Alive);
+}
+
+#[test]
+fn stamp_line_places_pattern_at_intervals() {
+    let mut universe = empty_universe();
+    universe.stamp_line("O", 10, 0, 10, 20, 5).unwrap();
+    assert_eq!(population(&universe), 5);
+    let cells = universe.get_cells();
+    for column in [0, 5, 10, 15, 20] {
+        assert_eq!(cells[10 * 120 + column], Cell::Alive);
+    }
+    assert!(universe.stamp_line("O", 0, 0, 0, 1, 0).is_err());
+}
+
+#[test]
+fn tick_for_runs_within_budget() {
+    let mut universe = Universe::new();
+    let generations = universe.tick_for(std::time::Duration::from_micros(1));
+    assert!(generations >= 1);
+    assert!(generations < 100);
+}
+
+#[test]
+fn diff_typed_separates_births_and_deaths() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(10, 9), (10, 10), (10, 11)]);
+    universe.tick();
+    let (mut births, mut deaths): (Vec<i32>, Vec<i32>) =
+        universe.diff_typed().into_iter().partition(|i| *i >= 0);
+    deaths.iter_mut().for_each(|i| *i = -*i - 1);
+    births.sort();
+    deaths.sort();
+    assert_eq!(births, vec![9 * 120 + 10, 11 * 120 + 10]);
+    assert_eq!(deaths, vec![10 * 120 + 9, 10 * 120 + 11]);
+}
+
+#[test]
+fn overpopulation_threshold_is_configurable() {
+    let plus = [(10, 10), (9, 10), (11, 10), (10, 9), (10, 11)];
+
+    let mut universe = empty_universe();
+    universe.set_cells(&plus);
+    universe.tick();
+    assert_eq!(universe.get_cells()[10 * 120 + 10], Cell::Dead);
+
+    let mut universe = empty_universe();
+    universe.set_overpopulation(4);
+    universe.set_cells(&plus);
+    universe.tick();
+    assert_eq!(universe.get_cells()[10 * 120 + 10], Cell::Alive);
+}
+
+#[test]
+fn transpose_swaps_coordinates() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(2, 5)]);
+    universe.transpose().unwrap();
+    assert_eq!(population(&universe), 1);
+    assert_eq!(universe.get_cells()[5 * 120 + 2], Cell::Alive);
+}
+
+#[test]
+fn alive_instance_data_is_normalized() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(0, 0), (60, 30)]);
+    assert_eq!(universe.alive_instance_data(), vec![0.0, 0.0, 0.25, 0.5]);
+}
+
+#[test]
+fn population_stats_track_extremes() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(10, 10), (10, 11), (11, 10)]);
+    universe.tick();
+    universe.tick();
+    assert_eq!(universe.population(), 4);
+    assert_eq!(universe.max_population(), 4);
+    assert_eq!(universe.min_population(), 0);
+
+    universe.clear();
+    assert_eq!(universe.max_population(), 0);
+}
+
+#[test]
+fn insert_scene_places_named_patterns() {
+    let mut universe = empty_universe();
+    universe
+        .insert_scene("glider 10 10\nglider 50 50\n")
+        .unwrap();
+    assert_eq!(population(&universe), 10);
+
+    assert!(universe.insert_scene("glider 80 80\nunicorn 5 5").is_err());
+    assert_eq!(population(&universe), 10);
+}
+
+#[test]
+fn deserialize_detects_corruption() {
+    let universe = Universe::new();
+    let mut bytes = universe.serialize();
+    let restored = Universe::deserialize(&bytes).unwrap();
+    assert_eq!(restored.get_cells(), universe.get_cells());
+
+    bytes[100] ^= 0xFF;
+    assert!(Universe::deserialize(&bytes).is_err());
+}
+
+#[test]
+fn base64_round_trip() {
+    let mut universe = empty_universe();
+    universe.insert_glider(10, 10).unwrap();
+    universe.insert_pulsar(60, 60).unwrap();
+    universe.set_cells(&[(0, 0), (119, 119)]);
+    let encoded = universe.to_base64();
+    assert!(encoded
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    let restored = Universe::from_base64(&encoded).unwrap();
+    assert_eq!(restored.get_cells(), universe.get_cells());
+    assert!(Universe::from_base64("not base64!").is_err());
+}
+
+#[test]
+fn prev_cells_hold_previous_generation() {
+    let mut universe = Universe::new();
+    let before = universe.get_cells().to_vec();
+    universe.tick();
+    let prev = unsafe { std::slice::from_raw_parts(universe.prev_cells(), 120 * 120) };
+    assert_eq!(prev, &before[..]);
+}
+
+#[test]
+fn tick_no_diff_matches_tick() {
+    let mut universe = Universe::new();
+    let mut copy = Universe::deserialize(&universe.serialize()).unwrap();
+    for _ in 0..5 {
+        universe.tick();
+        copy.tick_no_diff();
+    }
+    assert_eq!(universe.get_cells(), copy.get_cells());
+}
+
+#[test]
+fn reserve_active_keeps_results() {
+    let mut universe = Universe::new();
+    let mut reserved = Universe::deserialize(&universe.serialize()).unwrap();
+    reserved.reserve_active(1000);
+    for _ in 0..5 {
+        universe.tick();
+        reserved.tick();
+        assert_eq!(universe.get_cells(), reserved.get_cells());
+    }
+}
+
+#[test]
+fn count_clusters_separates_blocks() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(10, 10), (10, 11), (11, 10), (11, 11)]);
+    universe.set_cells(&[(50, 50), (50, 51), (51, 50), (51, 51)]);
+    assert_eq!(universe.count_clusters(), 2);
+
+    let mut universe = empty_universe();
+    universe.set_cells(&[(0, 0), (119, 119)]);
+    assert_eq!(universe.count_clusters(), 1);
+}
+
+#[test]
+fn largest_cluster_size_picks_biggest() {
+    let mut universe = empty_universe();
+    assert_eq!(universe.largest_cluster_size(), 0);
+    universe.set_cells(&[(10, 10), (10, 11), (11, 10), (11, 11)]);
+    universe.set_cells(&[(50, 49), (50, 50), (50, 51)]);
+    assert_eq!(universe.largest_cluster_size(), 4);
+}
+
+#[test]
+fn pattern_leading_blank_lines_are_ignored() {
+    let mut universe = empty_universe();
+    universe
+        .try_insert_pattern("!Comment\n\n\nO", 0, 0)
+        .unwrap();
+    assert_eq!(universe.get_cells()[0], Cell::Alive);
+    assert_eq!(population(&universe), 1);
+}
+
+#[test]
+fn pattern_trailing_blank_lines_are_ignored() {
+    let mut universe = empty_universe();
+    universe.set_boundary(Boundary::Dead);
+    universe.try_insert_pattern("O\n\n\n", 119, 0).unwrap();
+    assert_eq!(universe.get_cells()[119 * 120], Cell::Alive);
+}
+
+#[test]
+fn pattern_internal_blank_lines_are_empty_rows() {
+    let mut universe = empty_universe();
+    universe.try_insert_pattern("O\n\nO", 0, 0).unwrap();
+    assert_eq!(universe.get_cells()[0], Cell::Alive);
+    assert_eq!(universe.get_cells()[2 * 120], Cell::Alive);
+    assert_eq!(population(&universe), 2);
+
+    let mut universe = empty_universe();
+    universe.insert_pulsar(60, 60).unwrap();
+    assert_eq!(population(&universe), 48);
+    for _ in 0..3 {
+        universe.tick();
+    }
+    assert_eq!(population(&universe), 48);
+}
+
+#[test]
+fn methuselahs_are_inserted() {
+    let mut universe = empty_universe();
+    universe.set_boundary(Boundary::Dead);
+    universe.insert_r_pentomino(60, 60).unwrap();
+    assert_eq!(population(&universe), 5);
+    for _ in 0..100 {
+        universe.tick();
+    }
+    assert!(population(&universe) > 0);
+
+    let mut universe = empty_universe();
+    universe.insert_acorn(60, 60).unwrap();
+    assert_eq!(population(&universe), 7);
+
+    let mut universe = empty_universe();
+    universe.insert_diehard(60, 60).unwrap();
+    assert_eq!(population(&universe), 7);
+}
+
+#[test]
+fn tick_timed_reports_duration() {
+    let mut universe = Universe::new();
+    assert!(universe.tick_timed() > 0);
+}
+
+#[test]
+fn display_renders_rows() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(0, 1)]);
+    let rendered = universe.to_string();
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines.len(), 120);
+    assert_eq!(lines[0], format!(".O{}", ".".repeat(118)));
+    assert_eq!(lines[1], ".".repeat(120));
+}
+
+#[test]
+fn from_cells_builds_board() {
+    let mut universe = Universe::from_cells(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+    assert_eq!((universe.width(), universe.height()), (5, 5));
+    assert_eq!(universe.population(), 3);
+    universe.tick();
+    assert_eq!(universe.to_string(), ".....\n..O..\n..O..\n..O..\n.....\n");
+}
+
+#[test]
+fn randomize_is_not_degenerate() {
+    let mut universe = Universe::new();
+    let first = universe.get_cells().to_vec();
+    let alive = universe.population();
+    assert!((6000..8400).contains(&alive), "{} alive cells", alive);
+    universe.randomize();
+    assert_ne!(universe.get_cells(), &first[..]);
+    assert_ne!(Universe::new().get_cells(), &first[..]);
+}
+
+#[test]
+fn preview_next_matches_diff() {
+    let mut universe = Universe::new();
+    let preview = universe.preview_next();
+    universe.tick();
+    let diff: Vec<i32> = universe
+        .diff_typed()
+        .into_iter()
+        .map(|i| if i < 0 { -i - 1 } else { i })
+        .collect();
+    assert!(!preview.is_empty());
+    assert_eq!(preview, diff);
+}
+
+#[test]
+fn immigration_birth_takes_majority_color() {
+    let mut universe = empty_universe();
+    universe.set_mode(Mode::Immigration);
+    universe.set_cells(&[(10, 9), (10, 10), (10, 11)]);
+    universe.set_cell_color(10, 9, 1).unwrap();
+    universe.tick();
+    assert_eq!(universe.get_cells()[9 * 120 + 10], Cell::Alive);
+    assert_eq!(universe.cell_color(9, 10), 0);
+    assert_eq!(universe.cell_color(11, 10), 0);
+
+    assert!(universe.set_cell_color(0, 0, 2).is_err());
+}
+
+#[test]
+fn single_parent_births_inherit_its_color() {
+    for color in 0..2 {
+        let mut universe = Universe::from_cells(7, 7, &[(3, 3)]);
+        universe.set_mode(Mode::Immigration);
+        universe.set_rule("B1/S").unwrap();
+        universe.set_cell_color(3, 3, color).unwrap();
+        universe.tick();
+        assert_eq!(universe.get_cell(2, 3), Cell::Alive);
+        assert_eq!(universe.cell_color(2, 3), color);
+    }
+
+    // Two different parents are a tie, not a reason to pick the missing color.
+    let mut universe = Universe::from_cells(7, 7, &[(3, 2), (3, 4)]);
+    universe.set_mode(Mode::QuadLife);
+    universe.set_rule("B2/S").unwrap();
+    universe.set_cell_color(3, 2, 1).unwrap();
+    universe.set_cell_color(3, 4, 3).unwrap();
+    universe.set_color_tiebreak(ColorTiebreak::Lowest);
+    universe.tick();
+    assert_eq!(universe.get_cell(2, 3), Cell::Alive);
+    assert_eq!(universe.cell_color(2, 3), 1);
+}
+
+#[test]
+fn quadlife_birth_colors() {
+    let mut universe = empty_universe();
+    universe.set_mode(Mode::QuadLife);
+    universe.set_cells(&[(10, 9), (10, 10), (10, 11)]);
+    universe.set_cell_color(10, 9, 2).unwrap();
+    universe.set_cell_color(10, 10, 3).unwrap();
+    universe.set_cell_color(10, 11, 3).unwrap();
+    universe.tick();
+    assert_eq!(universe.cell_color(9, 10), 3);
+
+    let mut universe = empty_universe();
+    universe.set_mode(Mode::QuadLife);
+    universe.set_cells(&[(10, 9), (10, 10), (10, 11)]);
+    universe.set_cell_color(10, 9, 0).unwrap();
+    universe.set_cell_color(10, 10, 1).unwrap();
+    universe.set_cell_color(10, 11, 3).unwrap();
+    universe.tick();
+    assert_eq!(universe.cell_color(9, 10), 2);
+    assert_eq!(universe.cell_color(11, 10), 2);
+}
+
+#[test]
+fn index_coords_round_trip() {
+    let universe = Universe::from_cells(7, 3, &[]);
+    assert_eq!(universe.index_to_coords(9), vec![1, 2]);
+    assert_eq!(universe.coords_to_index(2, 6), 20);
+    for index in [0, 6, 7, 13, 20] {
+        let coords = universe.index_to_coords(index);
+        assert_eq!(universe.coords_to_index(coords[0], coords[1]), index);
+    }
+}
+
+#[test]
+fn wrapped_changes_flag_seam_influence() {
+    // A vertical blinker on the first column spawns cells on both sides of the seam.
+    let mut universe = Universe::from_cells(10, 10, &[(4, 0), (5, 0), (6, 0)]);
+    universe.tick();
+    let wrapped = universe.wrapped_changes();
+    assert!(wrapped.contains(&universe.coords_to_index(5, 9)));
+    assert!(!wrapped.contains(&universe.coords_to_index(5, 1)));
+
+    let mut universe = Universe::from_cells(10, 10, &[(4, 4), (5, 4), (6, 4)]);
+    universe.tick();
+    assert!(universe.wrapped_changes().is_empty());
+}
+
+#[test]
+fn toggle_cells_flat_twice_restores_board() {
+    let mut universe = Universe::new();
+    let before = universe.get_cells().to_vec();
+    universe.toggle_cells_flat(&[1, 2, 3, 4]).unwrap();
+    assert_ne!(universe.get_cells(), &before[..]);
+    assert_eq!(universe.diff_typed().len(), 2);
+    universe.toggle_cells_flat(&[1, 2, 3, 4]).unwrap();
+    assert_eq!(universe.get_cells(), &before[..]);
+
+    universe.toggle_cells_flat(&[5, 5, 5, 5]).unwrap();
+    assert!(universe.diff_typed().is_empty());
+    assert!(universe.toggle_cells_flat(&[1, 2, 3]).is_err());
+    assert!(universe.toggle_cells_flat(&[1, 200]).is_err());
+}
+
+#[test]
+fn extract_wrapped_recenters_across_seam() {
+    let mut universe = empty_universe();
+    universe.set_cells(&[(119, 10), (119, 11), (0, 10), (0, 11)]);
+    let pattern = universe.extract_wrapped().unwrap();
+    assert_eq!((pattern.width(), pattern.height()), (2, 2));
+    assert_eq!(pattern.to_string(), "OO\nOO\n");
+
+    let mut universe = empty_universe();
+    universe.set_cells(&[(5, 119), (5, 0), (7, 1)]);
+    let pattern = universe.extract_wrapped().unwrap();
+    assert_eq!(pattern.to_string(), "OO.\n...\n..O\n");
+
+    assert!(empty_universe().extract_wrapped().is_err());
+}
+
+#[test]
+fn new_without_hook_constructs() {
+    let universe = Universe::new_without_hook();
+    assert_eq!((universe.width(), universe.height()), (120, 120));
+    assert!(universe.population() > 0);
+}
+
+#[test]
+fn run_to_samples_population() {
+    let mut universe = Universe::from_cells(10, 10, &[(5, 4), (5, 5), (5, 6)]);
+    assert_eq!(universe.run_to(10, 2), vec![3; 5]);
+    assert_eq!(universe.generation(), 10);
+    assert!(universe.run_to(5, 1).is_empty());
+
+    universe.clear();
+    assert_eq!(universe.generation(), 0);
+}
+
+#[test]
+fn to_svg_draws_live_cells() {
+    let universe = Universe::from_cells(4, 3, &[(0, 0), (1, 2), (2, 3)]);
+    let svg = universe.to_svg(10);
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("width=\"40\" height=\"30\""));
+    assert_eq!(svg.matches("<rect").count(), 3);
+    assert!(svg.contains("<rect x=\"20\" y=\"10\" width=\"10\" height=\"10\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn row_population_counts_single_row() {
+    let universe = Universe::from_cells(8, 4, &[(1, 0), (1, 3), (1, 7), (2, 2)]);
+    assert_eq!(universe.row_population(1), 3);
+    assert_eq!(universe.row_population(2), 1);
+    assert_eq!(universe.row_population(0), 0);
+}
+
+#[test]
+fn fade_buffer_decays_dead_cells() {
+    let mut universe = Universe::from_cells(5, 5, &[(2, 2)]);
+    let fade = |u: &Universe| unsafe { *u.fade_buffer().add(2 * 5 + 2) };
+    assert_eq!(fade(&universe), 0);
+    universe.tick();
+    assert_eq!(fade(&universe), 255);
+    universe.decay_fade(100);
+    assert_eq!(fade(&universe), 155);
+    universe.decay_fade(200);
+    assert_eq!(fade(&universe), 0);
+}
+
+#[test]
+fn goto_generation_restores_snapshots() {
+    let mut universe = Universe::new();
+    universe.set_snapshot_interval(5);
+    let mut history = vec![universe.get_cells().to_vec()];
+    for _ in 0..12 {
+        universe.tick();
+        history.push(universe.get_cells().to_vec());
+    }
+    for gen in [5, 7, 0, 12, 3] {
+        universe.goto_generation(gen).unwrap();
+        assert_eq!(universe.generation(), gen);
+        assert_eq!(universe.get_cells(), &history[gen as usize][..]);
+    }
+
+    let mut universe = Universe::new();
+    universe.run_to(3, 0);
+    assert!(universe.goto_generation(1).is_err());
+}
+
+#[test]
+fn insert_rejects_patterns_larger_than_grid() {
+    let mut universe = empty_universe();
+    let wide = "O".repeat(200);
+    let error = universe.try_insert_pattern(&wide, 0, 0).unwrap_err();
+    assert_eq!(
+        error,
+        LifeError::DimensionMismatch {
+            expected: (120, 120),
+            found: (200, 1)
+        }
+    );
+    assert!(error.to_string().contains("200x1"), "{}", error);
+    assert!(universe.stamp_line(&wide, 0, 0, 0, 10, 1).is_err());
+    assert_eq!(population(&universe), 0);
+
+    let mut universe = Universe::from_cells(5, 5, &[]);
+    assert!(universe.insert_pulsar(2, 2).is_err());
+    assert!(universe.insert_scene("glider 1 1\npulsar 2 2").is_err());
+    assert_eq!(universe.population(), 0);
+}
+
+#[test]
+fn insert_wraps_huge_coordinates() {
+    let (row, column) = (u32::MAX % 120, u32::MAX % 120);
+    let mut expected = empty_universe();
+    expected.insert_glider(row, 0).unwrap();
+    let mut universe = empty_universe();
+    universe.insert_glider(u32::MAX, 0).unwrap();
+    assert_eq!(universe.get_cells(), expected.get_cells());
+
+    let mut expected = empty_universe();
+    expected.insert_pattern_checked("OO", 0, column).unwrap();
+    expected.insert_bitmap(&[0b11], 2, 1, row, column).unwrap();
+    let mut universe = empty_universe();
+    universe.insert_pattern_checked("OO", 0, u32::MAX).unwrap();
+    universe
+        .insert_bitmap(&[0b11], 2, 1, u32::MAX, u32::MAX)
+        .unwrap();
+    assert_eq!(universe.get_cells(), expected.get_cells());
+}
+
+#[test]
+fn config_summary_lists_settings() {
+    let mut universe = Universe::from_cells(30, 20, &[]);
+    assert!(universe.set_rule("B36/S23").is_ok());
+    assert!(universe.set_rule("B9/S23").is_err());
+    assert!(universe.set_rule("Conway").is_err());
+    universe.set_boundary(Boundary::Dead);
+    universe.tick();
+    let summary = universe.config_summary();
+    assert!(summary.contains("rule: B36/S23"), "{}", summary);
+    assert!(summary.contains("width: 30"), "{}", summary);
+    assert!(summary.contains("height: 20"), "{}", summary);
+    assert!(summary.contains("boundary: Dead"), "{}", summary);
+    assert!(summary.contains("generation: 1"), "{}", summary);
+}
+
+#[test]
+fn sparse_storage_matches_dense() {
+    let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    let mut dense = Universe::from_cells(20, 16, &glider);
+    let mut sparse = SparseUniverse::new(20, 16);
+    sparse.set_cells(&glider);
+    for _ in 0..50 {
+        dense.tick();
+        sparse.tick();
+        let dense_cells: Vec<(u32, u32)> = dense
+            .get_cells()
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == Cell::Alive)
+            .map(|(i, _)| (i as u32 / 20, i as u32 % 20))
+            .collect();
+        assert_eq!(sparse.live_cells(), dense_cells);
+    }
+    assert_eq!(sparse.population(), 5);
+    assert!(sparse.set_rule("B0/S8").is_err());
+}
+
+#[test]
+fn diff_view_has_exact_length() {
+    let mut universe = Universe::from_cells(10, 10, &[(5, 4), (5, 5), (5, 6)]);
+    universe.tick();
+    assert_eq!(universe.diff_count(), 4);
+    let view = universe.diff_view();
+    assert_eq!(view.len(), 4);
+    assert!(view.iter().all(|i| *i >= 0));
+    universe.tick_no_diff();
+    assert_eq!(universe.diff_count(), 4);
+}
+
+#[test]
+fn find_predecessor_on_tiny_board() {
+    let block = [(1, 1), (1, 2), (2, 1), (2, 2)];
+    let mut universe = Universe::from_cells(4, 4, &block);
+    universe.set_boundary(Boundary::Dead);
+    let predecessor = universe.find_predecessor(1 << 16).unwrap();
+    let mut check = Universe::from_cells(4, 4, &predecessor);
+    check.set_boundary(Boundary::Dead);
+    check.tick();
+    assert_eq!(check.get_cells(), universe.get_cells());
+
+    assert_eq!(universe.find_predecessor(1), None);
+    assert_eq!(Universe::new().find_predecessor(1000), None);
+}
+
+#[test]
+fn tags_follow_cell_lifetime() {
+    let mut universe = Universe::from_cells(6, 6, &[(1, 1), (1, 2), (2, 1), (2, 2), (4, 4)]);
+    universe.tag_cell(1, 1, 7);
+    universe.tag_cell(4, 4, 9);
+    universe.tick();
+    let tag = |u: &Universe, i: usize| unsafe { *u.tag_ptr().add(i) };
+    assert_eq!(tag(&universe, 6 + 1), 7);
+    assert_eq!(tag(&universe, 4 * 6 + 4), 0);
+}
+
+#[cfg(feature = "image-import")]
+#[test]
+fn from_png_marks_dark_pixels() {
+    // 4x3 grayscale image with dark pixels at (0, 0), (1, 2) and (2, 3).
+    let png = [
+        137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 4, 0, 0, 0, 3, 8, 0,
+        0, 0, 0, 145, 159, 241, 26, 0, 0, 0, 19, 73, 68, 65, 84, 120, 218, 99, 96, 248, 255, 255,
+        63, 16, 115, 129, 136, 255, 41, 0, 68, 117, 9, 102, 234, 13, 103, 150, 0, 0, 0, 0, 73, 69,
+        78, 68, 174, 66, 96, 130,
+    ];
+    let universe = Universe::from_png(&png, 128).unwrap();
+    assert_eq!((universe.width(), universe.height()), (4, 3));
+    assert_eq!(universe.to_string(), "O...\n..O.\n...O\n");
+    assert_eq!(Universe::from_png(&png, 50).unwrap().population(), 2);
+
+    let mut corrupted = png;
+    corrupted[45] ^= 0xFF;
+    assert!(Universe::from_png(&corrupted, 128).is_err());
+    assert!(Universe::from_png(b"not a png", 128).is_err());
+}
+
+/// 1x1 grayscale PNG whose image data is the given DEFLATE stream.
+#[cfg(feature = "image-import")]
+fn one_pixel_png(deflate: &[u8]) -> Vec<u8> {
+    let mut png = vec![137, 80, 78, 71, 13, 10, 26, 10];
+    let mut chunk = |kind: &[u8], data: &[u8]| {
+        let body = [kind, data].concat();
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        png.extend_from_slice(&body);
+        png.extend_from_slice(&crc32(&body).to_be_bytes());
+    };
+    chunk(b"IHDR", &[0, 0, 0, 1, 0, 0, 0, 1, 8, 0, 0, 0, 0]);
+    chunk(b"IDAT", &[&[0x78, 0x01], deflate].concat());
+    chunk(b"IEND", &[]);
+    png
+}
+
+#[cfg(feature = "image-import")]
+#[test]
+fn from_png_limits_decompressed_size() {
+    // A stored block with the filter byte and one black pixel.
+    let stored = [1, 2, 0, 0xFD, 0xFF, 0, 0];
+    assert_eq!(
+        Universe::from_png(&one_pixel_png(&stored), 128)
+            .unwrap()
+            .population(),
+        1
+    );
+    let mut bad_complement = stored;
+    bad_complement[3] = 0;
+    assert!(Universe::from_png(&one_pixel_png(&bad_complement), 128).is_err());
+    let too_long = [1, 3, 0, 0xFC, 0xFF, 0, 0, 0];
+    assert!(Universe::from_png(&one_pixel_png(&too_long), 128).is_err());
+
+    // A fixed Huffman block repeating one byte 25 800 times.
+    let mut bits = vec![1, 1, 0];
+    let mut code = |value: u32, length: u32| {
+        bits.extend((0..length).rev().map(|i| (value >> i & 1) as u8));
+    };
+    code(0x30, 8);
+    for _ in 0..100 {
+        code(0xC5, 8);
+        code(0, 5);
+    }
+    code(0, 7);
+    let bomb: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().rev().fold(0, |acc, bit| acc << 1 | bit))
+        .collect();
+    assert!(matches!(
+        Universe::from_png(&one_pixel_png(&bomb), 128),
+        Err(LifeError::InvalidData(_))
+    ));
+}
+
+#[cfg(feature = "image-import")]
+#[test]
+fn from_png_rejects_oversized_chunk_lengths() {
+    let mut png = vec![137, 80, 78, 71, 13, 10, 26, 10];
+    png.extend_from_slice(&(u32::MAX - 4).to_be_bytes());
+    png.extend_from_slice(b"IHDR");
+    png.extend_from_slice(&[0; 17]);
+    assert!(matches!(
+        Universe::from_png(&png, 128),
+        Err(LifeError::InvalidData(_))
+    ));
+}
+
+#[test]
+fn tick_region_freezes_outside_cells() {
+    // Vertical blinker inside the region, a lone cell just outside it.
+    let start = [(1, 2), (2, 2), (3, 2), (2, 5)];
+    let mut universe = Universe::from_cells(7, 5, &start);
+    universe.tick_region(0, 0, 5, 5);
+    assert_eq!(
+        universe.to_string(),
+        ".......\n.......\n.OOO.O.\n.......\n.......\n"
+    );
+    universe.tick_region(0, 0, 5, 5);
+    assert_eq!(
+        universe.get_cells(),
+        Universe::from_cells(7, 5, &start).get_cells()
+    );
+}
+
+#[test]
+fn diff_against_lists_differing_cells() {
+    let a = Universe::from_cells(5, 5, &[(1, 1), (2, 2)]);
+    let b = Universe::from_cells(5, 5, &[(1, 1), (2, 2)]);
+    assert_eq!(a.diff_against(&b), Ok(vec![]));
+
+    let c = Universe::from_cells(5, 5, &[(1, 1)]);
+    assert_eq!(a.diff_against(&c), Ok(vec![2 * 5 + 2]));
+    assert!(a.diff_against(&Universe::from_cells(4, 5, &[])).is_err());
+}
+
+#[test]
+fn randomize_clustered_population_is_close_to_requested() {
+    let mut universe = Universe::new();
+    universe.randomize_clustered(42, 10, 20);
+    let population = universe.population();
+    assert!(
+        (150..=200).contains(&population),
+        "population {}",
+        population
+    );
+    assert!(universe.count_clusters() <= 10);
+
+    let mut again = Universe::new();
+    again.randomize_clustered(42, 10, 20);
+    assert_eq!(again.get_cells(), universe.get_cells());
+}
+
+#[test]
+fn tick_with_events_reports_milestones() {
+    let mut universe = Universe::from_cells(5, 5, &[(2, 2)]);
+    assert_eq!(universe.tick_with_events(), ["extinct"]);
+    assert!(universe.tick_with_events().is_empty());
+
+    let mut block = Universe::from_cells(6, 6, &[(1, 1), (1, 2), (2, 1)]);
+    assert_eq!(block.tick_with_events(), ["population-peak"]);
+    assert_eq!(block.tick_with_events(), ["stabilized"]);
+    assert!(block.tick_with_events().is_empty());
+}
+
+#[test]
+fn toroidal_distance_wraps_around_edges() {
+    let universe = Universe::from_cells(10, 8, &[]);
+    assert_eq!(universe.toroidal_distance(0, 0, 7, 9), 1);
+    assert_eq!(universe.toroidal_distance(3, 0, 3, 9), 1);
+    assert_eq!(universe.toroidal_distance(0, 0, 4, 5), 5);
+    assert_eq!(universe.toroidal_distance(2, 2, 2, 2), 0);
+}
+
+#[test]
+fn from_rle_inserts_centered_pattern() {
+    let glider = "#N Glider\n#C A comment\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!\n";
+    let universe = Universe::from_rle(glider, 8, 8, 4, 4).unwrap();
+    assert_eq!(universe.population(), 5);
+    assert_eq!(
+        universe.to_string(),
+        "........\n........\n........\n....O...\n.....O..\n...OOO..\n........\n........\n"
+    );
+
+    assert!(Universe::from_rle("x = 3, y = 3\n3b!", 8, 8, 4, 4).is_err());
+    assert!(Universe::from_rle("bqo!", 8, 8, 4, 4).is_err());
+    assert!(Universe::from_rle(glider, 2, 2, 1, 1).is_err());
+    assert!(matches!(
+        Universe::from_rle(glider, 65536, 65536, 0, 0),
+        Err(LifeError::InvalidArgument(_))
+    ));
+    assert!(Universe::from_rle(glider, 0, 8, 0, 0).is_err());
+}
+
+#[test]
+fn from_rle_rejects_oversized_runs() {
+    for rle in [
+        "3000000000b3000000000bo!",
+        "3000000000$3000000000$o!",
+        "5000o4000$5000o!",
+        "x = 100000, y = 100000
+o!",
+    ] {
+        assert!(
+            matches!(Pattern::from_rle(rle), Err(LifeError::ParseError { .. })),
+            "{}",
+            rle
+        );
+    }
+    assert_eq!(Pattern::from_rle("4096o!").unwrap().population(), 4096);
+}
+
+#[test]
+fn memory_footprint_grows_with_board() {
+    let mut universe = Universe::from_cells(10, 10, &[(1, 1)]);
+    let small = universe.memory_footprint();
+    assert!(small >= 100 * 4);
+    universe.set_width(40);
+    universe.set_height(30);
+    assert_eq!((universe.width(), universe.height()), (40, 30));
+    assert_eq!(universe.population(), 0);
+    assert!(universe.memory_footprint() >= small * 12);
+}
+
+#[test]
+fn accumulated_diff_covers_several_ticks() {
+    // A glider changes different cells on each tick.
+    let glider = [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)];
+    let mut reference = Universe::from_cells(8, 8, &glider);
+    reference.tick();
+    let mut expected = reference.diff_view();
+    reference.tick();
+    expected.extend(reference.diff_view());
+    expected.sort_unstable();
+    expected.dedup();
+
+    let mut universe = Universe::from_cells(8, 8, &glider);
+    universe.set_diff_accumulate(true);
+    universe.tick();
+    universe.tick();
+    let mut accumulated = universe.diff_view();
+    accumulated.sort_unstable();
+    assert_eq!(accumulated, expected);
+    assert!(accumulated.len() > reference.diff_view().len());
+
+    universe.clear_diff();
+    assert_eq!(universe.diff_count(), 0);
+    universe.set_diff_accumulate(false);
+    universe.tick();
+    universe.tick();
+    assert_eq!(universe.diff_count() as usize, reference.diff_view().len());
+}
+
+#[test]
+fn checkerboard_alternates_cells() {
+    let mut universe = Universe::from_cells(4, 3, &[]);
+    universe.init_checkerboard();
+    assert_eq!(universe.to_string(), "O.O.\n.O.O\nO.O.\n");
+    assert_eq!(universe.population(), 6);
+}
+
+#[test]
+fn stripes_repeat_with_period() {
+    let mut universe = Universe::from_cells(7, 2, &[]);
+    universe.init_stripes(2);
+    assert_eq!(universe.to_string(), "OO..OO.\nOO..OO.\n");
+    universe.init_stripes(1);
+    assert_eq!(universe.to_string(), "O.O.O.O\nO.O.O.O\n");
+}
+
+#[test]
+fn pattern_reports_population() {
+    let mut universe = empty_universe();
+    universe.insert_glider(10, 10).unwrap();
+    let glider = universe.extract_pattern(9, 9, 3, 3).unwrap();
+    assert_eq!(glider.population(), 5);
+    assert_eq!("OO\nOO".parse::<Pattern>().unwrap().population(), 4);
+}
+
+#[test]
+fn or_blend_keeps_background() {
+    let glider = ".O.\n..O\nOOO";
+    let mut universe = Universe::from_cells(5, 5, &[]);
+    universe.init_checkerboard();
+    universe
+        .insert_pattern_blended(glider, 2, 2, Blend::Or)
+        .unwrap();
+    assert_eq!(universe.to_string(), "O.O.O\n.OOO.\nO.OOO\n.OOO.\nO.O.O\n");
+
+    universe.init_checkerboard();
+    universe
+        .insert_pattern_blended(glider, 2, 2, Blend::Overwrite)
+        .unwrap();
+    assert_eq!(universe.to_string(), "O.O.O\n..O..\nO..OO\n.OOO.\nO.O.O\n");
+}
+
+#[test]
+fn seam_cells_are_the_border() {
+    let universe = Universe::from_cells(3, 3, &[]);
+    assert_eq!(universe.seam_cells(), [0, 1, 2, 3, 5, 6, 7, 8]);
+    assert_eq!(Universe::from_cells(4, 1, &[]).seam_cells(), [0, 1, 2, 3]);
+}
+
+#[test]
+fn async_tick_is_reproducible_and_differs_from_sync() {
+    let soup = |u: &mut Universe| u.randomize_clustered(7, 6, 12);
+    let mut sync = Universe::from_cells(16, 16, &[]);
+    soup(&mut sync);
+    let mut first = Universe::from_cells(16, 16, &[]);
+    soup(&mut first);
+    let mut second = Universe::from_cells(16, 16, &[]);
+    soup(&mut second);
+
+    sync.tick();
+    first.tick_async(99);
+    second.tick_async(99);
+    assert_eq!(first.get_cells(), second.get_cells());
+    assert_ne!(first.get_cells(), sync.get_cells());
+    assert_eq!(first.generation(), 1);
+    assert!(first.diff_count() > 0);
+}
+
+#[test]
+fn noise_skips_some_changes() {
+    let mut clean = Universe::from_cells(32, 32, &[]);
+    clean.randomize_clustered(3, 20, 15);
+    let mut noisy = Universe::from_cells(32, 32, &[]);
+    noisy.randomize_clustered(3, 20, 15);
+    noisy.set_noise(0.5, 0.5, 11).unwrap();
+
+    clean.tick();
+    noisy.tick();
+    let changes = clean.diff_count();
+    assert!(noisy.diff_count() < changes);
+    assert!(noisy.diff_count() > 0);
+
+    let mut again = Universe::from_cells(32, 32, &[]);
+    again.randomize_clustered(3, 20, 15);
+    again.set_noise(0.5, 0.5, 11).unwrap();
+    again.tick();
+    assert_eq!(again.get_cells(), noisy.get_cells());
+
+    assert!(noisy.set_noise(1.5, 1.0, 0).is_err());
+    assert!(noisy.set_noise(f64::NAN, 1.0, 0).is_err());
+}
+
+#[test]
+fn block_entropy_measures_disorder() {
+    let mut universe = Universe::from_cells(32, 32, &[]);
+    assert_eq!(universe.block_entropy(4), 0.0);
+    universe.init_checkerboard();
+    assert_eq!(universe.block_entropy(4), 0.0);
+
+    universe.randomize();
+    assert!(universe.block_entropy(4) > 1.0);
+    assert_eq!(universe.block_entropy(0), 0.0);
+}
+
+#[test]
+fn seeded_stream_reproduces_randomize_sequence() {
+    let mut first = Universe::new();
+    let mut second = Universe::new();
+    first.seed_stream(2024);
+    second.seed_stream(2024);
+    let mut boards = vec![];
+    for _ in 0..3 {
+        first.randomize();
+        second.randomize();
+        assert_eq!(first.get_cells(), second.get_cells());
+        boards.push(first.get_cells().to_vec());
+    }
+    assert_ne!(boards[0], boards[1]);
+    assert_ne!(boards[1], boards[2]);
+}
+
+#[test]
+fn to_rows_lays_out_rows() {
+    let universe = Universe::from_cells(3, 2, &[(0, 1), (1, 0), (1, 2)]);
+    assert_eq!(universe.to_rows(), [[0, 1, 0], [1, 0, 1]]);
+}
+
+#[test]
+fn clear_kills_every_cell() {
+    let mut universe = Universe::new();
+    universe.init_checkerboard();
+    universe.clear();
+    assert!(universe.get_cells().iter().all(|c| *c == Cell::Dead));
+    assert_eq!(universe.max_population(), 0);
+}
+
+#[test]
+fn neighbor_rendering_uses_palette_entry() {
+    let palette = [
+        0x0000_00FF,
+        0x1111_11FF,
+        0x2222_22FF,
+        0xFF00_33FF,
+        0x4444_44FF,
+        0x5555_55FF,
+        0x6666_66FF,
+        0x7777_77FF,
+        0x8888_88FF,
+    ];
+    // Every cell of a block has three live neighbors.
+    let universe = Universe::from_cells(6, 6, &[(1, 1), (1, 2), (2, 1), (2, 2)]);
+    let pixels = universe.render_rgba_by_neighbors(&palette);
+    assert_eq!(pixels.len(), 6 * 6 * 4);
+    let at = |row: usize, column: usize| &pixels[(row * 6 + column) * 4..][..4];
+    assert_eq!(at(1, 1), [0xFF, 0x00, 0x33, 0xFF]);
+    assert_eq!(at(0, 0), [0, 0, 0, 0]);
+}
+
+#[test]
+fn origin_offsets_views() {
+    let mut universe = Universe::from_cells(4, 3, &[(1, 2), (2, 3)]);
+    universe.set_origin(1, 2);
+    assert_eq!(universe.get_cell(0, 0), Cell::Alive);
+    assert_eq!(universe.get_cell(1, 1), Cell::Alive);
+    assert_eq!(universe.get_cell(2, 2), Cell::Dead);
+    assert_eq!(universe.to_string(), "O...\n.O..\n....\n");
+    assert_eq!(universe.to_rows()[1], [0, 1, 0, 0]);
+
+    universe.tick();
+    let mut changed = universe.diff_view();
+    changed.sort_unstable();
+    assert_eq!(changed, [0, 5]);
+}
+
+#[test]
+fn origin_offsets_every_view_like_moving_the_cells() {
+    // Shifting the origin must look exactly like moving every cell by the opposite amount.
+    let cells = [(1, 1), (1, 2), (1, 3), (5, 6), (6, 6), (7, 0)];
+    let (width, height, origin) = (9, 8, (2, 3));
+    let mut scrolled = Universe::from_cells(width, height, &cells);
+    scrolled.set_origin(origin.0, origin.1);
+    let moved: Vec<(u32, u32)> = cells
+        .iter()
+        .map(|(r, c)| {
+            (
+                (r + height - origin.0) % height,
+                (c + width - origin.1) % width,
+            )
+        })
+        .collect();
+    let mut moved = Universe::from_cells(width, height, &moved);
+    for _ in 0..3 {
+        scrolled.tick();
+        moved.tick();
+    }
+    assert_eq!(scrolled.to_string(), moved.to_string());
+    assert_eq!(scrolled.to_rows(), moved.to_rows());
+    let sorted = |mut diff: Vec<i32>| {
+        diff.sort_unstable();
+        diff
+    };
+    assert_eq!(sorted(scrolled.diff_view()), sorted(moved.diff_view()));
+    assert_eq!(sorted(scrolled.diff_typed()), sorted(moved.diff_typed()));
+    let changes = |universe: &Universe| match universe.diff_or_full(100) {
+        DiffResult::Changes(changes) => sorted(changes.into_iter().map(|i| i as i32).collect()),
+        DiffResult::Full => unreachable!(),
+    };
+    assert_eq!(changes(&scrolled), changes(&moved));
+    let palette = [0x1111_11FF, 2, 3, 0x4444_44FF, 5, 6, 7, 8, 9];
+    assert_eq!(
+        scrolled.render_rgba_by_neighbors(&palette),
+        moved.render_rgba_by_neighbors(&palette)
+    );
+    assert_eq!(
+        scrolled.render_age_rgba(0x00FF_00FF, 0xFF00_00FF, 4),
+        moved.render_age_rgba(0x00FF_00FF, 0xFF00_00FF, 4)
+    );
+    assert_eq!(scrolled.to_svg(3), moved.to_svg(3));
+    assert_eq!(scrolled.alive_instance_data(), moved.alive_instance_data());
+
+    let storage = scrolled.coords_to_index(0, 0);
+    assert_eq!(storage, 2 * width + 3);
+    assert_eq!(scrolled.index_to_coords(storage), [0, 0]);
+    assert_eq!(scrolled.index_to_coords(0), [height - 2, width - 3]);
+}
+
+#[test]
+fn cell_period_of_blinker_cells() {
+    let universe = Universe::from_cells(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+    assert_eq!(universe.cell_period(2, 2, 4), Some(1));
+    assert_eq!(universe.cell_period(2, 1, 4), Some(2));
+    assert_eq!(universe.cell_period(1, 2, 4), Some(2));
+    assert_eq!(universe.cell_period(0, 0, 4), Some(1));
+    assert_eq!(universe.generation(), 0);
+
+    // A glider passing by changes the cell once, which never repeats in time.
+    let glider = Universe::from_cells(8, 8, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    assert_eq!(glider.cell_period(2, 0, 3), None);
+}
+
+#[test]
+fn rle_export_round_trips() {
+    let glider = [(3, 4), (4, 5), (5, 3), (5, 4), (5, 5)];
+    let universe = Universe::from_cells(10, 10, &glider);
+    assert_eq!(universe.live_bounds(), Some((3, 3, 3, 3)));
+    let rle = universe.to_rle();
+    assert_eq!(rle, "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+
+    let imported = Universe::from_rle(&rle, 10, 10, 4, 4).unwrap();
+    assert_eq!(imported.get_cells(), universe.get_cells());
+
+    let gappy = Universe::from_cells(10, 10, &[(0, 0), (3, 2)]);
+    assert_eq!(gappy.to_rle(), "x = 3, y = 4, rule = B3/S23\no3$2bo!\n");
+    assert_eq!(Universe::from_cells(4, 4, &[]).live_bounds(), None);
+}
+
+#[test]
+fn checked_insert_refuses_overlap() {
+    let glider = ".O.\n..O\nOOO";
+    let mut universe = Universe::from_cells(10, 10, &[]);
+    universe.insert_pattern_checked(glider, 4, 4).unwrap();
+    let error = universe
+        .insert_pattern_checked(glider, 4, 5)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("overlaps 2 live cells"), "{}", error);
+    assert_eq!(universe.population(), 5);
+
+    universe.insert_pattern_checked(glider, 4, 8).unwrap();
+    assert_eq!(universe.population(), 10);
+}
+
+#[test]
+fn partition_dynamic_separates_block_and_blinker() {
+    let block = [(1, 1), (1, 2), (2, 1), (2, 2)];
+    let blinker = [(6, 5), (6, 6), (6, 7)];
+    let universe = Universe::from_cells(10, 10, &[&block[..], &blinker[..]].concat());
+    let (oscillating, still) = universe.partition_dynamic(2);
+    assert_eq!(still, [11, 12, 21, 22, 66]);
+    assert_eq!(oscillating, [56, 65, 67, 76]);
+    assert_eq!(universe.generation(), 0);
+}
+
+#[test]
+fn mobius_seam_flips_rows() {
+    // On a 5x5 strip, the cell left of (0, 0) is (4, 4).
+    let mut universe = Universe::from_cells(5, 5, &[(3, 4), (4, 4)]);
+    universe.set_boundary(Boundary::Mobius);
+    universe.toggle_cell(0, 0);
+    universe.toggle_cell(1, 0);
+    // (0, 0) and (1, 0) now see each other plus (4, 4) and (3, 4) across the seam,
+    // so each of the four cells has three neighbors and survives; nothing wraps vertically.
+    universe.tick();
+    assert_eq!(universe.population(), 4);
+    assert_eq!(universe.get_cell(0, 0), Cell::Alive);
+
+    let mut torus = Universe::from_cells(5, 5, &[(3, 4), (4, 4), (0, 0), (1, 0)]);
+    torus.tick();
+    assert_ne!(torus.get_cells(), universe.get_cells());
+
+    let mut sparse = SparseUniverse::new(5, 5);
+    sparse.set_boundary(Boundary::Mobius);
+    sparse.set_cells(&[(3, 4), (4, 4), (0, 0), (1, 0)]);
+    sparse.tick();
+    assert_eq!(sparse.live_cells(), [(0, 0), (1, 0), (3, 4), (4, 4)]);
+}
+
+#[test]
+fn local_population_counts_box_around_point() {
+    let universe = Universe::from_cells(10, 10, &[(5, 5), (4, 6), (7, 3), (9, 9), (0, 0)]);
+    assert_eq!(universe.local_population(5, 5, 0), 1);
+    assert_eq!(universe.local_population(5, 5, 1), 2);
+    assert_eq!(universe.local_population(5, 5, 2), 3);
+    assert_eq!(universe.local_population(0, 9, 1), 2);
+    assert_eq!(universe.local_population(0, 0, 100), 5);
+    // Coordinates wrap around like the box does: u32::MAX is row and column 5.
+    assert_eq!(universe.local_population(u32::MAX, u32::MAX, 1), 2);
+    assert_eq!(universe.local_population(u32::MAX, 0, 0), 0);
+}
+
+#[test]
+fn blinker_population_sequence_is_constant() {
+    let mut universe = Universe::from_cells(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+    assert_eq!(universe.populations(6), [3; 6]);
+    assert_eq!(universe.generation(), 6);
+}
+
+#[test]
+fn set_line_draws_diagonal() {
+    let mut universe = Universe::from_cells(8, 8, &[]);
+    universe.set_line(1, 1, 5, 5, Cell::Alive);
+    assert_eq!(universe.population(), 5);
+    for (row, column) in [(1, 1), (3, 3), (5, 5)] {
+        assert_eq!(universe.get_cell(row, column), Cell::Alive);
+    }
+    universe.set_line(3, 3, 3, 3, Cell::Dead);
+    assert_eq!(universe.get_cell(3, 3), Cell::Dead);
+
+    universe.set_line(7, 6, 9, 8, Cell::Alive);
+    assert_eq!(universe.get_cell(1, 0), Cell::Alive);
+}
+
+#[test]
+fn ticked_universe_passes_invariants() {
+    let mut universe = Universe::new();
+    universe.randomize();
+    universe.tick();
+    assert_eq!(universe.debug_invariants(), Ok(()));
+    universe.set_diff_accumulate(true);
+    universe.tick();
+    universe.toggle_cells_flat(&[0, 0, 1, 1]).unwrap();
+    assert_eq!(universe.debug_invariants(), Ok(()));
+    universe.set_width(30);
+    assert_eq!(universe.debug_invariants(), Ok(()));
+}
+
+#[test]
+fn insert_at_fraction_snaps_to_cell() {
+    let mut universe = empty_universe();
+    assert_eq!(universe.width(), 120);
+    universe.insert_at_fraction("O", 0.5, 0.25).unwrap();
+    assert_eq!(universe.get_cell(30, 60), Cell::Alive);
+    assert_eq!(universe.population(), 1);
+    universe.insert_at_fraction("O", 0.9999, 0.0).unwrap();
+    assert_eq!(universe.get_cell(0, 119), Cell::Alive);
+    assert!(universe.insert_at_fraction("O", 1.0, 0.5).is_err());
+    assert!(universe.insert_at_fraction("O", 0.5, -0.1).is_err());
+}
+
+#[test]
+fn overlay_merges_smaller_board() {
+    let glider = Universe::from_cells(3, 3, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    let mut universe = Universe::from_cells(10, 10, &[(0, 0)]);
+    universe.overlay(&glider, 4, 5).unwrap();
+    assert_eq!(universe.population(), 6);
+    assert_eq!(universe.get_cell(4, 6), Cell::Alive);
+    assert_eq!(universe.get_cell(6, 5), Cell::Alive);
+
+    universe.overlay(&glider, 8, 9).unwrap();
+    assert_eq!(universe.get_cell(9, 1), Cell::Alive);
+    assert_eq!(universe.get_cell(0, 9), Cell::Alive);
+
+    let mut small = Universe::from_cells(3, 3, &[]);
+    assert!(small.overlay(&universe, 0, 0).is_err());
+}
+
+#[test]
+fn diff_or_full_signals_full_redraw() {
+    let mut universe = Universe::from_cells(20, 20, &[]);
+    universe.init_checkerboard();
+    universe.tick();
+    assert_eq!(universe.diff_count(), 200);
+    assert_eq!(universe.diff_or_full(100), DiffResult::Full);
+
+    let mut blinker = Universe::from_cells(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+    blinker.tick();
+    assert_eq!(
+        blinker.diff_or_full(100),
+        DiffResult::Changes(vec![7, 11, 13, 17])
+    );
+}
+
+#[test]
+fn has_predecessor_detects_orphan() {
+    // On a 3x3 torus every cell sees the whole board, so a lone cell can't be reached.
+    let orphan = Universe::from_cells(3, 3, &[(1, 1)]);
+    assert_eq!(orphan.has_predecessor(), Ok(false));
+
+    // Any three cells fill the board on the next tick.
+    let mut full = Universe::from_cells(3, 3, &[(0, 0), (1, 1), (2, 2)]);
+    full.tick();
+    assert_eq!(full.population(), 9);
+    assert_eq!(full.has_predecessor(), Ok(true));
+
+    assert!(Universe::from_cells(5, 5, &[]).has_predecessor().is_err());
+}
+
+#[test]
+fn cell_size_round_trips() {
+    let mut universe = Universe::new();
+    assert_eq!(universe.cell_size(), 5);
+    universe.set_cell_size(12);
+    assert_eq!(universe.cell_size(), 12);
+    universe.set_width(10);
+    assert_eq!(universe.cell_size(), 12);
+}
+
+#[test]
+fn row_sum_tick_matches_scalar_tick() {
+    for &(width, height) in [(37, 23), (1, 5), (2, 2), (8, 1)].iter() {
+        for boundary in [Boundary::Toroidal, Boundary::Dead, Boundary::Mobius].iter() {
+            let mut scalar = Universe::from_cells(width, height, &[]);
+            scalar.seed_stream(5);
+            scalar.randomize();
+            scalar.set_boundary(*boundary);
+            let mut row_sums = Universe::deserialize(&scalar.serialize()).unwrap();
+            row_sums.set_boundary(*boundary);
+            row_sums.set_tick_strategy(TickStrategy::RowSums);
+            for _ in 0..10 {
+                scalar.tick();
+                row_sums.tick();
+                assert_eq!(row_sums.get_cells(), scalar.get_cells());
+                assert_eq!(row_sums.diff_view(), scalar.diff_view());
+            }
+        }
+    }
+}
+
+#[test]
+fn frozen_universe_ignores_ticks_and_edits() {
+    let mut universe = Universe::from_cells(6, 6, &[(2, 1), (2, 2), (2, 3)]);
+    let before = universe.to_string();
+    universe.freeze();
+    universe.tick();
+    universe.toggle_cell(0, 0);
+    universe.set_cells(&[(5, 5)]);
+    universe.clear();
+    assert_eq!(universe.to_string(), before);
+    assert_eq!(universe.generation(), 0);
+    assert!(universe.insert_glider(3, 3).is_err());
+    assert!(universe.toggle_cells_flat(&[0, 0]).is_err());
+
+    universe.unfreeze();
+    universe.tick();
+    assert_ne!(universe.to_string(), before);
+}
+
+#[cfg(feature = "image-export")]
+#[test]
+fn capture_frames_returns_png_per_tick() {
+    let mut universe = Universe::from_cells(6, 6, &[(2, 1), (2, 2), (2, 3)]);
+    let frames = universe.capture_frames(3, 2);
+    assert_eq!(frames.len(), 3);
+    assert_eq!(universe.generation(), 3);
+    for frame in &frames {
+        assert_eq!(frame[..8], [137, 80, 78, 71, 13, 10, 26, 10]);
+        assert_eq!(frame[16..24], [0, 0, 0, 12, 0, 0, 0, 12]);
+    }
+
+    #[cfg(feature = "image-import")]
+    {
+        let decoded = Universe::from_png(&frames[0], 128).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (12, 12));
+        assert_eq!(decoded.population(), 3 * 4);
+    }
+}
+
+#[cfg(feature = "image-export")]
+#[test]
+fn capture_frames_limits_scale() {
+    use std::convert::TryInto;
+
+    let mut universe = Universe::from_cells(10, 5, &[(2, 2)]);
+    let frame = &universe.capture_frames(1, u32::MAX)[0];
+    let width = u32::from_be_bytes(frame[16..20].try_into().unwrap());
+    let height = u32::from_be_bytes(frame[20..24].try_into().unwrap());
+    assert_eq!(width, 2 * height);
+    assert!(width == 2890 && height == 1445, "{}x{}", width, height);
+
+    let mut universe = Universe::from_cells(5000, 1000, &[]);
+    let frame = &universe.capture_frames(1, 8)[0];
+    assert_eq!(frame[16..24], [0, 0, 0x13, 0x88, 0, 0, 0x03, 0xE8]);
+}
+
+#[test]
+fn extract_all_crops_to_live_cells() {
+    let universe = Universe::from_cells(20, 20, &[(7, 8), (8, 9), (9, 7), (9, 8), (9, 9)]);
+    let glider = universe.extract_all();
+    assert_eq!((glider.width(), glider.height()), (3, 3));
+    assert_eq!(glider.to_string(), ".O.\n..O\nOOO\n");
+
+    let empty = Universe::from_cells(5, 5, &[]).extract_all();
+    assert_eq!(
+        (empty.width(), empty.height(), empty.population()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn rule_region_runs_highlife_inside() {
+    // Dead cells with six live neighbors: born under HighLife (B36/S23), not Conway.
+    let six = [(1, 1), (1, 2), (1, 3), (3, 1), (3, 2), (3, 3)];
+    let shifted: Vec<_> = six.iter().map(|(r, c)| (*r, c + 6)).collect();
+    let mut universe = Universe::from_cells(12, 5, &[&six[..], &shifted[..]].concat());
+    universe.set_rule_region(0, 0, 6, 5, "B36/S23").unwrap();
+    universe.tick();
+    assert_eq!(universe.get_cell(2, 2), Cell::Alive);
+    assert_eq!(universe.get_cell(2, 8), Cell::Dead);
+
+    assert!(universe.set_rule_region(0, 0, 1, 1, "nonsense").is_err());
+}
+
+#[test]
+fn generations_since_change_counts_idle_ticks() {
+    let mut universe = Universe::from_cells(6, 6, &[(1, 1), (1, 2), (2, 1), (2, 2)]);
+    assert_eq!(universe.generations_since_change(), 0);
+    universe.tick();
+    universe.tick();
+    universe.tick();
+    assert_eq!(universe.generations_since_change(), 3);
+    universe.toggle_cell(4, 4);
+    assert_eq!(universe.generations_since_change(), 0);
+    universe.tick();
+    assert_eq!(universe.generations_since_change(), 0);
+    universe.tick();
+    assert_eq!(universe.generations_since_change(), 1);
+}
+
+#[test]
+fn age_rendering_interpolates_colors() {
+    let (young, old) = (0x00FF_00FF, 0xFF00_00FF);
+    // A blinker's center survives while its ends are reborn every tick.
+    let mut universe = Universe::from_cells(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+    universe.tick();
+    universe.tick();
+    assert_eq!(universe.cell_age(2, 2), 2);
+    assert_eq!(universe.cell_age(2, 1), 0);
+
+    let pixels = universe.render_age_rgba(young, old, 4);
+    let at = |row: usize, column: usize| &pixels[(row * 5 + column) * 4..][..4];
+    assert_eq!(at(2, 1), [0x00, 0xFF, 0x00, 0xFF]);
+    assert_eq!(at(2, 2), [0x7F, 0x80, 0x00, 0xFF]);
+    assert_eq!(at(0, 0), [0, 0, 0, 0]);
+
+    let capped = universe.render_age_rgba(young, old, 1);
+    assert_eq!(capped[(2 * 5 + 2) * 4..][..4], [0xFF, 0x00, 0x00, 0xFF]);
+
+    let zero_cap = universe.render_age_rgba(young, old, 0);
+    assert_eq!(zero_cap[(2 * 5 + 2) * 4..][..4], [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(zero_cap[(2 * 5 + 1) * 4..][..4], [0xFF, 0x00, 0x00, 0xFF]);
+    assert_eq!(zero_cap[..4], [0, 0, 0, 0]);
+}
+
+#[test]
+fn insert_bitmap_stamps_block() {
+    let mut universe = Universe::from_cells(6, 6, &[]);
+    universe.insert_bitmap(&[0b1111], 2, 2, 2, 3).unwrap();
+    assert_eq!(universe.population(), 4);
+    assert_eq!(
+        universe.to_string(),
+        "......\n......\n...OO.\n...OO.\n......\n......\n"
+    );
+
+    // Glider in a 3x3 bitmap spans two bytes.
+    universe
+        .insert_bitmap(&[0b1110_0010, 0b1], 3, 3, 5, 5)
+        .unwrap();
+    assert_eq!(universe.population(), 9);
+    assert!(universe.insert_bitmap(&[0xFF], 3, 3, 0, 0).is_err());
+}
+
+#[test]
+fn live_perimeter_of_solid_block() {
+    let block: Vec<_> = (1..4).flat_map(|r| (1..4).map(move |c| (r, c))).collect();
+    let mut universe = Universe::from_cells(6, 6, &block);
+    assert_eq!(universe.live_perimeter(), 8);
+
+    let mut corner = Universe::from_cells(
+        3,
+        3,
+        &block
+            .iter()
+            .map(|(r, c)| (r - 1, c - 1))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(corner.live_perimeter(), 0);
+    corner.set_boundary(Boundary::Dead);
+    assert_eq!(corner.live_perimeter(), 8);
+    universe.clear();
+    assert_eq!(universe.live_perimeter(), 0);
+}
+
+#[test]
+fn color_tiebreak_is_deterministic() {
+    // Under B36 the center is born from three parents of color 1 and three of color 2.
+    let parents = [(0, 0), (0, 1), (0, 2), (2, 0), (2, 1), (2, 2)];
+    let mut universe = Universe::from_cells(5, 5, &parents);
+    universe.set_rule("B36/S23").unwrap();
+    universe.set_mode(Mode::QuadLife);
+    for (i, (row, column)) in parents.iter().enumerate() {
+        universe
+            .set_cell_color(*row, *column, if i < 3 { 1 } else { 2 })
+            .unwrap();
+    }
+    let mut lowest = universe.clone();
+    lowest.set_color_tiebreak(ColorTiebreak::Lowest);
+    let replica = lowest.clone();
+    let mut highest = universe.clone();
+
+    lowest.tick();
+    highest.tick();
+    assert_eq!(lowest.cell_color(1, 1), 1);
+    assert_eq!(highest.cell_color(1, 1), 2);
+
+    let mut replica = replica;
+    replica.tick();
+    assert_eq!(replica.cell_color(1, 1), 1);
+    assert_eq!(replica.get_cells(), lowest.get_cells());
+}
+
+#[test]
+fn downsample_counts_cells_per_block() {
+    let mut universe = Universe::from_cells(10, 7, &[(5, 8)]);
+    let grid = universe.downsample(4);
+    // 3 blocks wide, 2 tall; the cell sits in the bottom right partial block.
+    assert_eq!(grid, vec![0, 0, 0, 0, 0, 1]);
+
+    universe.set_cells(&[(0, 0), (1, 1), (3, 3)]);
+    assert_eq!(universe.downsample(4)[0], 3);
+    assert_eq!(universe.downsample(1).len(), 70);
+    assert!(universe.downsample(0).is_empty());
+}
+
+#[test]
+fn replay_reproduces_recorded_session() {
+    let mut universe = Universe::from_cells(16, 12, &[(0, 0), (0, 1)]);
+    universe.set_boundary(Boundary::Dead);
+    universe.start_recording();
+    universe.toggle_cell(0, 2);
+    universe.insert_glider(6, 6).unwrap();
+    universe.tick();
+    universe.tick_region(0, 0, 8, 8);
+    universe.seed_stream(42);
+    universe.randomize();
+    universe.tick();
+    universe.tick_async(7);
+    universe.insert_pattern_checked("OO\nOO", 10, 13).ok();
+    universe.tick();
+    let log = universe.stop_recording();
+    assert!(log.starts_with("size 16 12\n"));
+
+    let replayed = Universe::replay(&log).unwrap();
+    assert_eq!(replayed.get_cells(), universe.get_cells());
+    assert_eq!(replayed.boundary(), Boundary::Dead);
+    assert!(Universe::replay("tick\n").is_err());
+    assert!(Universe::replay("size 4 4\nwarp 9\n").is_err());
+}
+
+#[test]
+fn errors_have_specific_variants() {
+    assert_eq!("...".parse::<Pattern>(), Err(LifeError::EmptyPattern));
+    assert!(matches!(
+        Pattern::from_rle("#C comment\nx = 3, y = 1\n2o\n3q!"),
+        Err(LifeError::ParseError { line: 4, .. })
+    ));
+
+    let mut universe = Universe::from_cells(8, 8, &[]);
+    assert_eq!(
+        universe.toggle_cells_flat(&[2, 9]),
+        Err(LifeError::OutOfBounds { row: 2, column: 9 })
+    );
+    assert_eq!(
+        universe.diff_against(&Universe::from_cells(8, 4, &[])),
+        Err(LifeError::DimensionMismatch {
+            expected: (8, 8),
+            found: (8, 4)
+        })
+    );
+    assert!(matches!(
+        universe.insert_scene("glider 1 1\n\nunicorn 2 2"),
+        Err(LifeError::ParseError { line: 3, .. })
+    ));
+    assert!(matches!(
+        Universe::deserialize(&[0; 4]),
+        Err(LifeError::InvalidData(_))
+    ));
+    universe.freeze();
+    assert_eq!(universe.insert_glider(3, 3), Err(LifeError::Frozen));
+
+    let error: Box<dyn std::error::Error> = Box::new(LifeError::Frozen);
+    assert_eq!(error.to_string(), "The universe is frozen");
+}
+
+#[test]
+fn period_spectrum_lists_distinct_oscillator_periods() {
+    let mut universe = Universe::from_cells(
+        40,
+        30,
+        &[(3, 2), (3, 3), (3, 4), (20, 30), (20, 31), (20, 32)],
+    );
+    universe.insert_pulsar(12, 14).unwrap();
+    universe.set_cells(&[(26, 2), (26, 3), (27, 2), (27, 3)]);
+    let before = universe.get_cells().to_vec();
+
+    assert_eq!(universe.period_spectrum(4), vec![2, 3]);
+    assert_eq!(universe.period_spectrum(2), vec![2]);
+    assert_eq!(universe.get_cells(), &before[..]);
+    assert!(Universe::from_cells(8, 8, &[])
+        .period_spectrum(4)
+        .is_empty());
+}
+
+#[test]
+fn find_pattern_locates_every_occurrence() {
+    let mut universe = Universe::from_cells(12, 10, &[]);
+    universe.try_insert_pattern("OO\nOO", 2, 3).unwrap();
+    universe.try_insert_pattern("OO\nOO", 9, 11).unwrap();
+    assert_eq!(
+        universe.find_pattern("OO\nOO").unwrap(),
+        vec![(2, 3), (9, 11)]
+    );
+    assert_eq!(universe.find_pattern("OOO").unwrap(), vec![]);
+
+    universe.set_boundary(Boundary::Dead);
+    assert_eq!(universe.find_pattern("OO\nOO").unwrap(), vec![(2, 3)]);
+    assert!(universe.find_pattern(&"O".repeat(13)).is_err());
+}
+
+#[test]
+fn scan_order_only_affects_async_ticks() {
+    let mut universe = Universe::from_cells(16, 16, &[]);
+    universe.seed_stream(3);
+    universe.randomize();
+    let orders = [
+        ScanOrder::RowMajor,
+        ScanOrder::ColumnMajor,
+        ScanOrder::BoustrophedonRow,
+    ];
+
+    let mut synchronous = universe.clone();
+    synchronous.populations(5);
+    for order in orders.iter() {
+        let mut ordered = universe.clone();
+        ordered.set_scan_order(*order);
+        ordered.populations(5);
+        assert_eq!(ordered.get_cells(), synchronous.get_cells());
+    }
+
+    let asynchronous: Vec<Vec<Cell>> = orders
+        .iter()
+        .map(|order| {
+            let mut ordered = universe.clone();
+            ordered.set_scan_order(*order);
+            ordered.tick_async(1);
+            let mut reseeded = universe.clone();
+            reseeded.set_scan_order(*order);
+            reseeded.tick_async(2);
+            assert_eq!(reseeded.get_cells(), ordered.get_cells());
+            ordered.get_cells().to_vec()
+        })
+        .collect();
+    assert_ne!(asynchronous[0], asynchronous[1]);
+    assert_ne!(asynchronous[0], asynchronous[2]);
+    assert_ne!(asynchronous[1], asynchronous[2]);
+}
+
+#[test]
+fn generation_delta_counts_changed_cells() {
+    let mut universe = Universe::from_cells(8, 8, &[(3, 2), (3, 3), (3, 4)]);
+    for _ in 0..3 {
+        universe.tick();
+        assert_eq!(universe.generation_delta(), 4);
+        assert_eq!(universe.generation_delta(), universe.diff_count());
+    }
+    universe.toggle_cell(0, 0);
+    assert_eq!(universe.generation_delta(), 5);
+}
+
+/// Exercises heavy allocation; run with `--no-default-features` to cover the system allocator.
+#[test]
+fn runs_with_selected_allocator() {
+    let mut universe = Universe::from_cells(256, 256, &[]);
+    universe.seed_stream(11);
+    universe.randomize();
+    universe.set_snapshot_interval(1);
+    universe.populations(20);
+    let restored = Universe::deserialize(&universe.serialize()).unwrap();
+    assert_eq!(restored.get_cells(), universe.get_cells());
+}
+
+#[test]
+fn tick_population_matches_population() {
+    let mut universe = Universe::from_cells(32, 32, &[]);
+    universe.insert_r_pentomino(16, 16).unwrap();
+    for _ in 0..30 {
+        let population = universe.tick_population();
+        assert_eq!(population, universe.population());
+    }
+    assert_eq!(universe.generation(), 30);
+    universe.freeze();
+    assert_eq!(universe.tick_population(), universe.population());
+}
+
+#[test]
+fn insert_pattern_mirrored_adds_reflected_copies() {
+    let glider = ".O.\n..O\nOOO";
+    let mut universe = Universe::from_cells(20, 20, &[]);
+    universe
+        .insert_pattern_mirrored(glider, 5, 4, Symmetry::Horizontal)
+        .unwrap();
+    assert_eq!(universe.population(), 10);
+    assert_eq!(universe.find_pattern(glider).unwrap(), vec![(4, 3)]);
+    assert_eq!(
+        universe.find_pattern(".O.\nO..\nOOO").unwrap(),
+        vec![(4, 14)]
+    );
+    for row in 0..20 {
+        for column in 0..20 {
+            assert_eq!(
+                universe.get_cell(row, column),
+                universe.get_cell(row, 19 - column)
+            );
+        }
+    }
+
+    let mut universe = Universe::from_cells(20, 20, &[]);
+    universe
+        .insert_pattern_mirrored(glider, 5, 4, Symmetry::Both)
+        .unwrap();
+    assert_eq!(universe.population(), 20);
+    assert_eq!(
+        universe.find_pattern("OOO\n..O\n.O.").unwrap(),
+        vec![(13, 3)]
+    );
+}
+
+#[test]
+fn neighbor_thresholds_follow_the_rule() {
+    let mut universe = empty_universe();
+    assert_eq!(universe.birth_neighbors(), vec![3]);
+    assert_eq!(universe.survival_neighbors(), vec![2, 3]);
+    universe.set_rule("B36/S125").unwrap();
+    assert_eq!(universe.birth_neighbors(), vec![3, 6]);
+    assert_eq!(universe.survival_neighbors(), vec![1, 2, 5]);
+}
+
+#[test]
+fn wrap_diff_flags_border_changes() {
+    let flag = 1 << 30;
+    let mut universe = Universe::from_cells(10, 10, &[]);
+    universe.toggle_cells_flat(&[0, 4, 5, 5]).unwrap();
+    assert_eq!(universe.get_diff(), &[4, 55]);
+
+    universe.set_wrap_diff(true);
+    universe.toggle_cells_flat(&[0, 4, 5, 5, 9, 9]).unwrap();
+    assert_eq!(universe.get_diff(), &[4 | flag, 55, 99 | flag]);
+    assert_eq!(universe.diff_view(), vec![4, 55, 99]);
+    universe.debug_invariants().unwrap();
+
+    universe.set_cells(&[(9, 3), (9, 4), (9, 5)]);
+    universe.tick();
+    // The blinker on the bottom row turns vertical across the seam,
+    // and the lone cell in the corner dies.
+    let mut entries = universe.get_diff().to_vec();
+    entries.sort_unstable();
+    let mut expected = vec![4 | flag, 84, 93 | flag, 95 | flag, 99 | flag];
+    expected.sort_unstable();
+    assert_eq!(entries, expected);
+    universe.clear_diff();
+    universe.debug_invariants().unwrap();
+}
+
+#[cfg(feature = "json-scene")]
+#[test]
+fn load_scene_json_inserts_rotated_patterns() {
+    let mut universe = Universe::from_cells(40, 40, &[]);
+    universe
+        .load_scene_json(
+            r#"[
+                {"pattern": "pulsar", "row": 10, "col": 10},
+                {"pattern": "OOO", "row": 30, "col": 30, "rotation": 90}
+            ]"#,
+        )
+        .unwrap();
+    assert_eq!(universe.population(), 48 + 3);
+    for (row, column) in [(29, 30), (30, 30), (31, 30)].iter() {
+        assert_eq!(universe.get_cell(*row, *column), Cell::Alive);
+    }
+    assert_eq!(universe.get_cell(30, 29), Cell::Dead);
+
+    let mut universe = Universe::from_cells(40, 40, &[]);
+    assert!(matches!(
+        universe.load_scene_json("[\n{\"pattern\": \"glider\", \"row\": 1}\n]"),
+        Err(LifeError::ParseError { line: 2, .. })
+    ));
+    assert!(universe
+        .load_scene_json(
+            r#"[{"pattern": "glider", "row": 1, "col": 1},
+            {"pattern": "glider", "row": 9, "col": 9, "rotation": 45}]"#
+        )
+        .is_err());
+    assert_eq!(universe.population(), 0);
+}
+
+#[test]
+fn ticks_until_extinct_counts_down_to_empty_board() {
+    let universe = Universe::from_cells(10, 10, &[(5, 5)]);
+    assert_eq!(universe.ticks_until_extinct(10), Some(1));
+    assert_eq!(universe.ticks_until_extinct(0), None);
+    assert_eq!(universe.population(), 1);
+    assert_eq!(universe.generation(), 0);
+
+    let mut universe = Universe::from_cells(40, 40, &[]);
+    universe.insert_diehard(20, 20).unwrap();
+    assert_eq!(universe.ticks_until_extinct(200), Some(130));
+    assert_eq!(universe.ticks_until_extinct(129), None);
+    assert_eq!(
+        Universe::from_cells(4, 4, &[]).ticks_until_extinct(0),
+        Some(0)
+    );
+    assert_eq!(
+        Universe::from_cells(4, 4, &[(1, 1), (1, 2), (2, 1), (2, 2)]).ticks_until_extinct(50),
+        None
+    );
+}
+
+#[test]
+fn oversized_boards_are_rejected() {
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&65536u32.to_le_bytes());
+    bytes.extend_from_slice(&65536u32.to_le_bytes());
+    bytes.push(0);
+    let checksum = crc32(&bytes);
+    bytes.extend_from_slice(&checksum.to_le_bytes());
+    assert!(matches!(
+        Universe::deserialize(&bytes),
+        Err(LifeError::InvalidData(_))
+    ));
+
+    assert!(matches!(
+        Universe::replay("size 65536 65536\n"),
+        Err(LifeError::ParseError { line: 1, .. })
+    ));
+    let mut universe = Universe::from_cells(8, 8, &[]);
+    assert!(matches!(
+        universe.insert_bitmap(&[0xFF], 65536, 65536, 0, 0),
+        Err(LifeError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn from_cells_raises_zero_sizes() {
+    let universe = Universe::from_cells(0, 0, &[]);
+    assert_eq!((universe.width(), universe.height()), (1, 1));
+    assert_eq!(universe.toroidal_distance(0, 0, 0, 0), 0);
+
+    let mut universe = Universe::from_cells(0, 3, &[]);
+    assert_eq!((universe.width(), universe.height()), (1, 3));
+    universe.randomize_clustered(7, 2, 2);
+    assert!(universe.population() <= 3);
+}
+
+#[test]
+fn replay_reproduces_region_rules_and_noise() {
+    let mut universe = Universe::from_cells(20, 20, &[]);
+    universe.seed_stream(5);
+    universe.randomize();
+    universe.set_rule_region(0, 0, 10, 20, "B36/S23").unwrap();
+    universe.set_noise(0.9, 0.8, 17).unwrap();
+    universe.populations(3);
+    universe.start_recording();
+    universe.populations(5);
+    universe.set_rule_region(10, 10, 5, 5, "B2/S").unwrap();
+    universe.set_noise(1.0, 1.0, 0).unwrap();
+    universe.populations(5);
+    let log = universe.stop_recording();
+
+    let replayed = Universe::replay(&log).unwrap();
+    assert_eq!(replayed.get_cells(), universe.get_cells());
+
+    // A B36 region makes the newborn at (1, 1) appear only when replayed with it.
+    let mut universe =
+        Universe::from_cells(6, 6, &[(0, 0), (0, 1), (0, 2), (2, 0), (2, 1), (2, 2)]);
+    universe.set_rule_region(1, 1, 1, 1, "B36/S23").unwrap();
+    universe.start_recording();
+    universe.tick();
+    let replayed = Universe::replay(&universe.stop_recording()).unwrap();
+    assert_eq!(replayed.get_cell(1, 1), Cell::Alive);
+    assert_eq!(replayed.get_cells(), universe.get_cells());
+}
+
+#[test]
+fn replay_reproduces_every_kind_of_edit() {
+    type Edit = fn(&mut Universe);
+    let edits: [(&str, Edit); 12] = [
+        ("set_cells", |u| u.set_cells(&[(4, 4), (4, 5), (4, 6)])),
+        ("toggle_cells_flat", |u| {
+            u.toggle_cells_flat(&[4, 4, 4, 5, 4, 6]).unwrap()
+        }),
+        ("set_line", |u| u.set_line(2, 2, 2, 9, Cell::Alive)),
+        ("overlay", |u| {
+            let other = Universe::from_cells(3, 3, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+            u.overlay(&other, 5, 5).unwrap()
+        }),
+        ("transpose", |u| u.transpose().unwrap()),
+        ("insert_pattern_blended", |u| {
+            u.insert_pattern_blended("OOO", 6, 6, Blend::Or).unwrap()
+        }),
+        ("insert_pattern_mirrored", |u| {
+            u.insert_pattern_mirrored("OO\n.O", 3, 3, Symmetry::Both)
+                .unwrap()
+        }),
+        ("insert_pattern_checked", |u| {
+            u.insert_pattern_checked("OOO", 9, 9).unwrap()
+        }),
+        ("try_insert_pattern", |u| {
+            u.try_insert_pattern("OO\nOO", 8, 2).unwrap()
+        }),
+        ("init_checkerboard", |u| u.init_checkerboard()),
+        ("init_stripes", |u| u.init_stripes(3)),
+        ("goto_generation", |u| u.goto_generation(1).unwrap()),
+    ];
+    for (name, edit) in edits.iter() {
+        let mut universe = Universe::from_cells(12, 12, &[(1, 1), (1, 2), (2, 1)]);
+        universe.set_mode(Mode::QuadLife);
+        universe.set_cell_color(1, 2, 3).unwrap();
+        universe.set_snapshot_interval(1);
+        universe.run_to(3, 0);
+        universe.start_recording();
+        edit(&mut universe);
+        universe.tick();
+        let replayed = Universe::replay(&universe.stop_recording()).unwrap();
+        assert_eq!(replayed.get_cells(), universe.get_cells(), "{}", name);
+        for row in 0..12 {
+            for column in 0..12 {
+                assert_eq!(
+                    replayed.cell_color(row, column),
+                    universe.cell_color(row, column),
+                    "{} at ({}, {})",
+                    name,
+                    row,
+                    column
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn cell_accessors_handle_out_of_range_cells() {
+    let mut universe = Universe::from_cells(8, 8, &[(7, 7)]);
+    universe.set_mode(Mode::QuadLife);
+    assert_eq!(
+        universe.set_cell_color(30, 0, 1),
+        Err(LifeError::OutOfBounds { row: 30, column: 0 })
+    );
+    assert_eq!(
+        universe.set_cell_color(0, 8, 1),
+        Err(LifeError::OutOfBounds { row: 0, column: 8 })
+    );
+    assert_eq!(universe.cell_color(30, 0), 0);
+    assert_eq!(universe.cell_age(0, 900), 0);
+    universe.tag_cell(8, 0, 3);
+    assert_eq!(universe.row_population(8), 0);
+    assert_eq!(universe.row_population(7), 1);
+    assert_eq!(universe.cell_period(0, 8, 4), None);
+}
+
+#[test]
+fn overpopulation_keeps_the_rest_of_the_rule() {
+    let mut universe = empty_universe();
+    universe.set_rule("B36/S125").unwrap();
+    universe.set_overpopulation(5);
+    assert_eq!(universe.rule(), "B36/S125");
+    universe.set_overpopulation(2);
+    assert_eq!(universe.rule(), "B36/S12");
+    universe.set_overpopulation(4);
+    assert_eq!(universe.rule(), "B36/S12");
+    universe.set_overpopulation(5);
+    assert_eq!(universe.rule(), "B36/S125");
+    universe.set_overpopulation(6);
+    assert_eq!(universe.rule(), "B36/S1256");
+
+    universe.set_rule("B3/S23").unwrap();
+    universe.set_overpopulation(4);
+    assert_eq!(universe.rule(), "B3/S234");
+    universe.set_overpopulation(1);
+    assert_eq!(universe.rule(), "B3/S");
+    universe.set_overpopulation(3);
+    assert_eq!(universe.rule(), "B3/S23");
+}
+
+#[test]
+fn stabilized_event_fires_with_accumulated_or_stale_diff() {
+    let mut block = Universe::from_cells(6, 6, &[(1, 1), (1, 2), (2, 1)]);
+    block.set_diff_accumulate(true);
+    assert_eq!(block.tick_with_events(), ["population-peak"]);
+    assert_eq!(block.tick_with_events(), ["stabilized"]);
+    assert!(block.tick_with_events().is_empty());
+
+    let mut block = Universe::from_cells(6, 6, &[(1, 1), (1, 2), (2, 1)]);
+    block.tick_no_diff();
+    assert_eq!(block.tick_with_events(), ["stabilized"]);
+    block.freeze();
+    assert!(block.tick_with_events().is_empty());
+}
+
+#[test]
+fn zero_sizes_are_ignored() {
+    let mut universe = Universe::from_cells(10, 8, &[(1, 1)]);
+    universe.set_width(0);
+    universe.set_height(0);
+    assert_eq!((universe.width(), universe.height()), (10, 8));
+    assert_eq!(universe.population(), 1);
+    assert_eq!(universe.toroidal_distance(0, 0, 7, 9), 1);
+    universe.insert_at_fraction("O", 0.99, 0.99).unwrap();
+    assert_eq!(universe.get_cell(7, 9), Cell::Alive);
+}
@@ -94,12 +94,17 @@ canvas.addEventListener("click", (event) => {
   const row = Math.min(Math.floor(canvasTop / (CELL_SIZE + 1)), height - 1);
   const col = Math.min(Math.floor(canvasLeft / (CELL_SIZE + 1)), width - 1);
 
-  if (event.ctrlKey) {
-    universe.insert_glider(row, col);
-  } else if (event.shiftKey) {
-    universe.insert_pulsar(row, col);
-  } else {
-    universe.toggle_cell(row, col);
+  try {
+    if (event.ctrlKey) {
+      universe.insert_glider(row, col);
+    } else if (event.shiftKey) {
+      universe.insert_pulsar(row, col);
+    } else {
+      universe.toggle_cell(row, col);
+    }
+  } catch (error) {
+    // Inserting fails when the pattern doesn't fit or the universe is frozen.
+    console.warn(error);
   }
 
   forceDrawCells();